@@ -0,0 +1,248 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Ident, LitStr, Type};
+
+/// Derives `maxstat::stat::StatMarker`.
+///
+/// ```ignore
+/// #[derive(StatMarker, PartialEq, Clone, Copy)]
+/// #[stat(raw = f32, name = "Health", default = 100., min = 0., max = 500.)]
+/// struct Health;
+/// ```
+///
+/// `raw` is required. `name` defaults to the type name, `default` sets the base of stats
+/// built with `Default`, `min` and `max` clamp the final value and `summation` picks a
+/// `Summation` variant.
+#[proc_macro_derive(StatMarker, attributes(stat))]
+pub fn derive_stat_marker(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Attrs {
+    raw: Option<Type>,
+    name: Option<LitStr>,
+    default: Option<Expr>,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    summation: Option<Ident>,
+}
+
+fn parse_attrs(input: &DeriveInput) -> syn::Result<Attrs> {
+    let mut attrs = Attrs::default();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("stat")) {
+        attr.parse_nested_meta(|meta| {
+            let key = meta
+                .path
+                .get_ident()
+                .map(Ident::to_string)
+                .unwrap_or_default();
+            match key.as_str() {
+                "raw" => attrs.raw = Some(meta.value()?.parse()?),
+                "name" => attrs.name = Some(meta.value()?.parse()?),
+                "default" => attrs.default = Some(meta.value()?.parse()?),
+                "min" => attrs.min = Some(meta.value()?.parse()?),
+                "max" => attrs.max = Some(meta.value()?.parse()?),
+                "summation" => attrs.summation = Some(meta.value()?.parse()?),
+                _ => return Err(meta.error("unknown stat attribute")),
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let attrs = parse_attrs(&input)?;
+    let raw = attrs.raw.ok_or_else(|| {
+        syn::Error::new_spanned(&input.ident, "missing `#[stat(raw = ...)]` attribute")
+    })?;
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let name = attrs
+        .name
+        .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+
+    let summation = attrs.summation.map(|s| {
+        quote! {
+            const SUMMATION: ::maxstat::raw::Summation = ::maxstat::raw::Summation::#s;
+        }
+    });
+
+    let default_base = attrs.default.map(|default| {
+        quote! {
+            fn default_base() -> ::core::option::Option<Self::Raw> {
+                ::core::option::Option::Some(#default)
+            }
+        }
+    });
+
+    let clamp = (attrs.min.is_some() || attrs.max.is_some()).then(|| {
+        let min = attrs.min.map(|min| {
+            quote! {
+                let value = if value < #min { #min } else { value };
+            }
+        });
+        let max = attrs.max.map(|max| {
+            quote! {
+                let value = if value > #max { #max } else { value };
+            }
+        });
+        quote! {
+            fn clamp(value: Self::Raw) -> Self::Raw {
+                #min
+                #max
+                value
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::maxstat::stat::StatMarker for #ident #ty_generics #where_clause {
+            type Raw = #raw;
+
+            #summation
+
+            fn name() -> &'static str {
+                #name
+            }
+
+            #default_base
+
+            #clamp
+        }
+    })
+}
+
+/// Derives `maxstat::block::StatBlock` for a struct whose fields are standard stats.
+///
+/// ```ignore
+/// #[derive(StatBlock)]
+/// #[stat_block(raw = f32, metadata = Source)]
+/// struct Character {
+///     health: StandardStatS<Health, Source>,
+///     mana: StandardStatS<Mana, Source>,
+///     #[stat_block(skip)]
+///     level: u32,
+/// }
+/// ```
+///
+/// Skipped fields are created with `Default` and left out of iteration.
+#[proc_macro_derive(StatBlock, attributes(stat_block))]
+pub fn derive_stat_block(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_block(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_block(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let mut raw: Option<Type> = None;
+    let mut metadata: Option<Type> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("stat_block"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("raw") {
+                raw = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("metadata") {
+                metadata = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unknown stat_block attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    let missing = |key| {
+        syn::Error::new_spanned(
+            &input.ident,
+            format!("missing `#[stat_block({key} = ...)]` attribute"),
+        )
+    };
+    let raw = raw.ok_or_else(|| missing("raw"))?;
+    let metadata = metadata.ok_or_else(|| missing("metadata"))?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "StatBlock needs named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "StatBlock can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut init = Vec::new();
+    let mut stats = Vec::new();
+    for field in fields {
+        let mut skip = false;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("stat_block"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown stat_block attribute"))
+                }
+            })?;
+        }
+
+        let ident = &field.ident;
+        let ty = &field.ty;
+        if skip {
+            init.push(quote! { #ident: ::core::default::Default::default() });
+        } else {
+            init.push(quote! {
+                #ident: <#ty as ::maxstat::block::DynStat<#raw, #metadata>>::from_base(
+                    bases
+                        .get(<#ty as ::maxstat::block::DynStat<#raw, #metadata>>::marker_name())
+                        .copied(),
+                )
+            });
+            stats.push(quote! { &self.#ident });
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::maxstat::block::StatBlock for #ident #ty_generics #where_clause {
+            type Raw = #raw;
+
+            type Metadata = #metadata;
+
+            fn from_bases(
+                bases: &::std::collections::HashMap<::std::string::String, #raw>,
+            ) -> Self {
+                Self {
+                    #(#init,)*
+                }
+            }
+
+            fn stats(&self) -> ::std::vec::Vec<&dyn ::maxstat::block::DynStat<#raw, #metadata>> {
+                ::std::vec![#(#stats),*]
+            }
+        }
+    })
+}
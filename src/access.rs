@@ -0,0 +1,301 @@
+use crate::{
+    cell::{standard::StandardStat, SharedCell, StatCell, ValueCell},
+    formula::StatFormula,
+    layer::{LayerList, Selector},
+    modifier::{
+        standard::{StandardFormula, StandardLayers},
+        Modifier,
+    },
+    raw::StandardRaw,
+    stat::{Stat, StatMarker},
+};
+
+/// Operations shared by [`Stat`], its cell wrappers and the standard stats, so generic code
+/// can take any of them.
+///
+/// Wrappers with a [`SharedCell`] policy also implement it for shared references, and
+/// `&mut S` does for any `S`, so a generic function taking `impl StatAccess` accepts
+/// `&mut stat` as well as `&*shared`.
+pub trait StatAccess {
+    type Marker: StatMarker;
+
+    type Layers: LayerList;
+
+    fn apply<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>;
+
+    fn remove<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>;
+
+    fn has<M, I>(&self, value: M) -> bool
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>;
+
+    /// Calls `f` on every modifier of the layer of `M`.
+    fn for_each<M, I>(&self, f: impl FnMut(&M))
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>;
+
+    fn base(&self) -> <Self::Marker as StatMarker>::Raw;
+
+    fn get(&self) -> <Self::Marker as StatMarker>::Raw;
+}
+
+impl<Marker, Layers, F> StatAccess for Stat<Marker, Layers, F>
+where
+    Marker: StatMarker,
+    Layers: LayerList,
+    F: StatFormula<Marker::Raw, Layers>,
+{
+    type Marker = Marker;
+
+    type Layers = Layers;
+
+    fn apply<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        Stat::apply(self, value);
+    }
+
+    fn remove<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        Stat::remove(self, value);
+    }
+
+    fn has<M, I>(&self, value: M) -> bool
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        Stat::has(self, value)
+    }
+
+    fn for_each<M, I>(&self, f: impl FnMut(&M))
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        self.layer::<M, I>().iter().for_each(f);
+    }
+
+    fn base(&self) -> Marker::Raw {
+        Stat::base(self)
+    }
+
+    fn get(&self) -> Marker::Raw {
+        Stat::get(self)
+    }
+}
+
+impl<S> StatAccess for &mut S
+where
+    S: StatAccess + ?Sized,
+{
+    type Marker = S::Marker;
+
+    type Layers = S::Layers;
+
+    fn apply<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        S::Layers: Selector<M, I>,
+    {
+        (**self).apply(value);
+    }
+
+    fn remove<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        S::Layers: Selector<M, I>,
+    {
+        (**self).remove(value);
+    }
+
+    fn has<M, I>(&self, value: M) -> bool
+    where
+        M: Modifier,
+        S::Layers: Selector<M, I>,
+    {
+        (**self).has(value)
+    }
+
+    fn for_each<M, I>(&self, f: impl FnMut(&M))
+    where
+        M: Modifier,
+        S::Layers: Selector<M, I>,
+    {
+        (**self).for_each(f);
+    }
+
+    fn base(&self) -> <S::Marker as StatMarker>::Raw {
+        (**self).base()
+    }
+
+    fn get(&self) -> <S::Marker as StatMarker>::Raw {
+        (**self).get()
+    }
+}
+
+/// Implements [`StatAccess`] for a wrapper with a cell policy `P`: owned wrappers write through
+/// `get_mut`, shared references to [`SharedCell`] wrappers through `write`.
+macro_rules! impl_stat_access {
+    ([$($g:tt)*] $ty:ty, $marker:ty, $layers:ty, where [$($bounds:tt)*]) => {
+        impl_stat_access!(
+            @impl [$($g)*] $ty, $ty, $marker, $layers, [P: ValueCell<<$marker as StatMarker>::Raw>, $($bounds)*],
+            |s: &mut $ty| P::get_mut(&mut s.0)
+        );
+        impl_stat_access!(
+            @impl ['a, $($g)*] &'a $ty, $ty, $marker, $layers, [P: SharedCell + ValueCell<<$marker as StatMarker>::Raw>, $($bounds)*],
+            |s: &$ty| P::write(&s.0)
+        );
+    };
+    (
+        @impl [$($g:tt)*] $self_ty:ty, $ty:ty, $marker:ty, $layers:ty, [$($bounds:tt)*],
+        |$w:ident: $w_ty:ty| $write:expr
+    ) => {
+        impl<$($g)*> StatAccess for $self_ty
+        where
+            $($bounds)*
+        {
+            type Marker = $marker;
+
+            type Layers = $layers;
+
+            fn apply<M, I>(&mut self, value: M)
+            where
+                M: Modifier,
+                $layers: Selector<M, I>,
+            {
+                let $w: $w_ty = self;
+                $write.apply(value);
+            }
+
+            fn remove<M, I>(&mut self, value: M)
+            where
+                M: Modifier,
+                $layers: Selector<M, I>,
+            {
+                let $w: $w_ty = self;
+                $write.remove(value);
+            }
+
+            fn has<M, I>(&self, value: M) -> bool
+            where
+                M: Modifier,
+                $layers: Selector<M, I>,
+            {
+                P::read(&self.0).has(value)
+            }
+
+            fn for_each<M, I>(&self, f: impl FnMut(&M))
+            where
+                M: Modifier,
+                $layers: Selector<M, I>,
+            {
+                P::read(&self.0).layer::<M, I>().iter().for_each(f);
+            }
+
+            fn base(&self) -> <$marker as StatMarker>::Raw {
+                P::read(&self.0).base()
+            }
+
+            fn get(&self) -> <$marker as StatMarker>::Raw {
+                P::value(&self.0)
+            }
+        }
+    };
+}
+
+impl_stat_access!(
+    [Marker, Layers, F, P] StatCell<Marker, Layers, F, P>, Marker, Layers,
+    where [Marker: StatMarker, Layers: LayerList, F: StatFormula<Marker::Raw, Layers>]
+);
+
+impl_stat_access!(
+    [Marker, Metadata, P, const N: usize] StandardStat<Marker, Metadata, P, N>, Marker,
+    StandardLayers<Marker, Metadata, N>,
+    where [
+        Marker: StatMarker,
+        Marker::Raw: StandardRaw,
+        Metadata: PartialEq + Clone + Copy,
+        StandardFormula: StatFormula<Marker::Raw, StandardLayers<Marker, Metadata, N>>,
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        layer::Layer,
+        layers,
+        modifier::standard::{Flat, Multiplicative},
+        non_send::{standard::StandardStatNS, StatNS},
+        send::{standard::StandardStatS, StatS},
+    };
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct DummyMarker;
+
+    impl StatMarker for DummyMarker {
+        type Raw = f32;
+    }
+
+    type Flat32 = Flat<DummyMarker, f32, ()>;
+    type More32 = Multiplicative<DummyMarker, f32, ()>;
+    type Layers = layers![Flat32, More32];
+
+    fn formula(base: f32, flat: &Layer<Flat32>, more: &Layer<More32>) -> f32 {
+        (base + Flat32::combine(flat)) * More32::combine(more)
+    }
+
+    /// Equips and unequips a ring on any kind of stat.
+    fn ring<S, I, J>(mut stat: S) -> (f32, f32, usize)
+    where
+        S: StatAccess<Marker = DummyMarker>,
+        S::Layers: Selector<Flat32, I> + Selector<More32, J>,
+    {
+        stat.apply(Flat32::from_raw(5.));
+        stat.apply(More32::from_raw(2.));
+        let equipped = stat.get();
+
+        let mut count = 0;
+        stat.for_each(|_: &Flat32| count += 1);
+        assert!(stat.has(Flat32::from_raw(5.)));
+
+        stat.remove(Flat32::from_raw(5.));
+        stat.remove(More32::from_raw(2.));
+        (equipped, stat.get(), count)
+    }
+
+    #[test]
+    fn test_access() {
+        let mut stat = Stat::<DummyMarker, Layers, _>::from_formula(10., formula);
+        assert_eq!((30., 10., 1), ring(&mut stat));
+
+        let shared = Arc::new(StatS::<DummyMarker, Layers, _>::from_formula(10., formula));
+        assert_eq!((30., 10., 1), ring(&*shared));
+
+        let local = StatNS::<DummyMarker, Layers, _>::from_formula(10., formula);
+        assert_eq!((30., 10., 1), ring(&local));
+
+        let standard = StandardStatS::<DummyMarker, ()>::new(10.);
+        assert_eq!((30., 10., 1), ring(&standard));
+
+        let mut standard = StandardStatNS::<DummyMarker, ()>::new(10.);
+        assert_eq!((30., 10., 1), ring(&mut standard));
+        assert_eq!(10., StatAccess::base(&standard));
+    }
+}
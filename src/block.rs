@@ -0,0 +1,203 @@
+use std::{collections::HashMap, fmt};
+
+#[cfg(feature = "derive")]
+pub use maxstat_derive::StatBlock;
+
+/// Combined layers of a standard stat, as shown by its `Display` implementation.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Breakdown<Raw> {
+    pub name: &'static str,
+    /// Expression of a stat not using the standard formula, shown instead of it.
+    pub formula: Option<&'static str>,
+    pub base: Raw,
+    pub flat: Raw,
+    pub additive: Raw,
+    pub post_add: Raw,
+    pub multiplicative: Raw,
+    pub post_mul: Raw,
+    pub value: Raw,
+}
+
+impl<Raw> Breakdown<Raw>
+where
+    Raw: fmt::Display,
+{
+    pub(crate) fn write_formula(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(formula) = self.formula {
+            return write!(f, "{formula} = {}", self.value);
+        }
+        write!(
+            f,
+            "((base {} + flat {}) * additive {} + post_add {}) * multiplicative {} + post_mul {} = {}",
+            self.base,
+            self.flat,
+            self.additive,
+            self.post_add,
+            self.multiplicative,
+            self.post_mul,
+            self.value,
+        )
+    }
+}
+
+impl<Raw> fmt::Display for Breakdown<Raw>
+where
+    Raw: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.name)?;
+        self.write_formula(f)
+    }
+}
+
+/// Object-safe view of a standard stat, letting a [`StatBlock`] handle stats of different
+/// markers uniformly.
+pub trait DynStat<Raw, Metadata> {
+    /// Creates the stat with `base`, falling back to the marker's default base.
+    fn from_base(base: Option<Raw>) -> Self
+    where
+        Self: Sized,
+        Raw: Default;
+
+    fn marker_name() -> &'static str
+    where
+        Self: Sized;
+
+    fn name(&self) -> &'static str;
+
+    fn base(&self) -> Raw;
+
+    fn get(&self) -> Raw;
+
+    fn clear(&self);
+
+    /// Removes every modifier carrying `source` as metadata.
+    fn clear_source(&self, source: Metadata);
+
+    fn breakdown(&self) -> Breakdown<Raw>;
+}
+
+/// Struct made of stats sharing a raw type and modifier metadata, usually derived.
+pub trait StatBlock {
+    type Raw;
+
+    type Metadata;
+
+    /// Creates every stat with the base found under its marker name in `bases`.
+    fn from_bases(bases: &HashMap<String, Self::Raw>) -> Self
+    where
+        Self: Sized;
+
+    fn stats(&self) -> Vec<&dyn DynStat<Self::Raw, Self::Metadata>>;
+
+    fn stat(&self, name: &str) -> Option<&dyn DynStat<Self::Raw, Self::Metadata>> {
+        self.stats().into_iter().find(|s| s.name() == name)
+    }
+
+    fn clear(&self) {
+        self.stats().into_iter().for_each(|s| s.clear());
+    }
+
+    fn clear_source(&self, source: Self::Metadata)
+    where
+        Self::Metadata: Copy,
+    {
+        self.stats()
+            .into_iter()
+            .for_each(|s| s.clear_source(source));
+    }
+
+    fn breakdown(&self) -> Vec<Breakdown<Self::Raw>> {
+        self.stats().into_iter().map(|s| s.breakdown()).collect()
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use super::*;
+    use crate::{
+        modifier::{
+            standard::{Additive, Flat},
+            Modifier,
+        },
+        send::standard::StandardStatS,
+        stat::StatMarker,
+    };
+
+    #[derive(PartialEq, Clone, Copy, Debug)]
+    enum Source {
+        Ring,
+        Buff,
+    }
+
+    #[derive(StatMarker, PartialEq, Clone, Copy)]
+    #[stat(raw = f32)]
+    struct Health;
+
+    #[derive(StatMarker, PartialEq, Clone, Copy)]
+    #[stat(raw = f32, default = 50.)]
+    struct Mana;
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct Armor;
+
+    impl StatMarker for Armor {
+        type Raw = f32;
+    }
+
+    #[derive(StatBlock)]
+    #[stat_block(raw = f32, metadata = Source)]
+    struct Character {
+        health: StandardStatS<Health, Source>,
+        mana: StandardStatS<Mana, Source>,
+        armor: StandardStatS<Armor, Source>,
+        #[stat_block(skip)]
+        level: u32,
+    }
+
+    #[test]
+    fn test_block() {
+        let character = Character::from_bases(&HashMap::from([("Health".to_string(), 100.)]));
+
+        assert_eq!(0, character.level);
+        assert_eq!(100., character.health.get());
+        assert_eq!(50., character.mana.get());
+
+        character
+            .health
+            .apply_flat(Flat::from_raw(10.).set_metadata(Some(Source::Ring)).build());
+        character.health.apply_additive(
+            Additive::from_raw(1.)
+                .set_metadata(Some(Source::Buff))
+                .build(),
+        );
+        character
+            .mana
+            .apply_flat(Flat::from_raw(5.).set_metadata(Some(Source::Buff)).build());
+
+        assert_eq!(220., character.stat("Health").unwrap().get());
+        assert_eq!(0., character.stat("Armor").unwrap().get());
+        assert!(character.stat("Stamina").is_none());
+
+        character.clear_source(Source::Buff);
+
+        assert_eq!(110., character.health.get());
+        assert_eq!(50., character.mana.get());
+        assert_eq!(
+            vec![
+                "Health: ((base 100 + flat 10) * additive 1 + post_add 0) * multiplicative 1 + post_mul 0 = 110",
+                "Mana: ((base 50 + flat 0) * additive 1 + post_add 0) * multiplicative 1 + post_mul 0 = 50",
+                "Armor: ((base 0 + flat 0) * additive 1 + post_add 0) * multiplicative 1 + post_mul 0 = 0",
+            ],
+            character
+                .breakdown()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+
+        character.clear();
+
+        assert_eq!(100., character.health.get());
+    }
+}
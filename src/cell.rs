@@ -0,0 +1,1099 @@
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex, MutexGuard, OnceLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
+};
+
+use crate::{
+    access::StatAccess,
+    error::Error,
+    formula::{Formula, StatFormula},
+    layer::{prefixed, FiniteLayers, HCons, Layer, LayerList, Selector},
+    modifier::Modifier,
+    raw::{AtomicRaw, Finite},
+    stat::{Stat, StatMarker},
+};
+
+pub mod standard;
+
+/// How a stat wrapper stores its stat, picking its synchronization model.
+///
+/// [`Plain`] owns the stat directly and is only modified through `&mut self`; the other
+/// policies are [`SharedCell`]s, modified through `&self`.
+///
+/// `read` and `write` panic when the stat is already mutably borrowed or its lock is
+/// poisoned; the `try_*` methods of the wrappers return [`Error::AlreadyBorrowed`] and
+/// [`Error::Poisoned`] instead. A poisoned stat stays poisoned until `clear_poison` is
+/// called, which keeps the modifiers as the panicking thread left them; `get_mut` and
+/// `into_inner` need no lock and ignore the poison. `parking_lot` locks are never poisoned.
+pub trait CellPolicy {
+    type Cell<T>;
+
+    type Ref<'a, T: 'a>: Deref<Target = T>;
+
+    fn new<T>(value: T) -> Self::Cell<T>;
+
+    fn read<'a, T: 'a>(cell: &'a Self::Cell<T>) -> Self::Ref<'a, T>;
+
+    fn try_read<'a, T: 'a>(cell: &'a Self::Cell<T>) -> Result<Self::Ref<'a, T>, Error> {
+        Ok(Self::read(cell))
+    }
+
+    fn clear_poison<T>(cell: &Self::Cell<T>) {
+        let _ = cell;
+    }
+
+    fn get_mut<T>(cell: &mut Self::Cell<T>) -> &mut T;
+
+    fn into_inner<T>(cell: Self::Cell<T>) -> T;
+}
+
+/// Policy whose cells can be written through a shared reference.
+pub trait SharedCell: CellPolicy {
+    type RefMut<'a, T: 'a>: DerefMut<Target = T>;
+
+    fn write<'a, T: 'a>(cell: &'a Self::Cell<T>) -> Self::RefMut<'a, T>;
+
+    fn try_write<'a, T: 'a>(cell: &'a Self::Cell<T>) -> Result<Self::RefMut<'a, T>, Error> {
+        Ok(Self::write(cell))
+    }
+}
+
+/// Policy reading the value of a stat with raw type `R`.
+///
+/// Every policy computes it under a read; [`Cached`] serves it without locking when the stat
+/// did not change since it was last computed.
+pub trait ValueCell<R>: CellPolicy {
+    fn value<T>(cell: &Self::Cell<T>) -> R
+    where
+        T: StatAccess,
+        T::Marker: StatMarker<Raw = R>;
+}
+
+/// Stat owned directly, without interior mutability or synchronization.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Plain;
+
+/// Stat behind a `RefCell`, for single-threaded sharing.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct WithRefCell;
+
+/// Stat behind a `Mutex`, for sharing between threads.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct WithMutex;
+
+/// Stat behind an `RwLock`, letting readers on different threads proceed together.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct WithRwLock;
+
+impl CellPolicy for Plain {
+    type Cell<T> = T;
+
+    type Ref<'a, T: 'a> = &'a T;
+
+    fn new<T>(value: T) -> T {
+        value
+    }
+
+    fn read<'a, T: 'a>(cell: &'a T) -> &'a T {
+        cell
+    }
+
+    fn get_mut<T>(cell: &mut T) -> &mut T {
+        cell
+    }
+
+    fn into_inner<T>(cell: T) -> T {
+        cell
+    }
+}
+
+impl CellPolicy for WithRefCell {
+    type Cell<T> = RefCell<T>;
+
+    type Ref<'a, T: 'a> = Ref<'a, T>;
+
+    fn new<T>(value: T) -> RefCell<T> {
+        RefCell::new(value)
+    }
+
+    fn read<'a, T: 'a>(cell: &'a RefCell<T>) -> Ref<'a, T> {
+        cell.borrow()
+    }
+
+    fn try_read<'a, T: 'a>(cell: &'a RefCell<T>) -> Result<Ref<'a, T>, Error> {
+        cell.try_borrow().map_err(|_| Error::AlreadyBorrowed)
+    }
+
+    fn get_mut<T>(cell: &mut RefCell<T>) -> &mut T {
+        cell.get_mut()
+    }
+
+    fn into_inner<T>(cell: RefCell<T>) -> T {
+        cell.into_inner()
+    }
+}
+
+impl SharedCell for WithRefCell {
+    type RefMut<'a, T: 'a> = RefMut<'a, T>;
+
+    fn write<'a, T: 'a>(cell: &'a RefCell<T>) -> RefMut<'a, T> {
+        cell.borrow_mut()
+    }
+
+    fn try_write<'a, T: 'a>(cell: &'a RefCell<T>) -> Result<RefMut<'a, T>, Error> {
+        cell.try_borrow_mut().map_err(|_| Error::AlreadyBorrowed)
+    }
+}
+
+impl CellPolicy for WithMutex {
+    type Cell<T> = Mutex<T>;
+
+    type Ref<'a, T: 'a> = MutexGuard<'a, T>;
+
+    fn new<T>(value: T) -> Mutex<T> {
+        Mutex::new(value)
+    }
+
+    fn read<'a, T: 'a>(cell: &'a Mutex<T>) -> MutexGuard<'a, T> {
+        cell.lock().unwrap()
+    }
+
+    fn try_read<'a, T: 'a>(cell: &'a Mutex<T>) -> Result<MutexGuard<'a, T>, Error> {
+        cell.lock().map_err(|_| Error::Poisoned)
+    }
+
+    fn clear_poison<T>(cell: &Mutex<T>) {
+        cell.clear_poison();
+    }
+
+    fn get_mut<T>(cell: &mut Mutex<T>) -> &mut T {
+        cell.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn into_inner<T>(cell: Mutex<T>) -> T {
+        cell.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl SharedCell for WithMutex {
+    type RefMut<'a, T: 'a> = MutexGuard<'a, T>;
+
+    fn write<'a, T: 'a>(cell: &'a Mutex<T>) -> MutexGuard<'a, T> {
+        cell.lock().unwrap()
+    }
+
+    fn try_write<'a, T: 'a>(cell: &'a Mutex<T>) -> Result<MutexGuard<'a, T>, Error> {
+        Self::try_read(cell)
+    }
+}
+
+impl CellPolicy for WithRwLock {
+    type Cell<T> = RwLock<T>;
+
+    type Ref<'a, T: 'a> = RwLockReadGuard<'a, T>;
+
+    fn new<T>(value: T) -> RwLock<T> {
+        RwLock::new(value)
+    }
+
+    fn read<'a, T: 'a>(cell: &'a RwLock<T>) -> RwLockReadGuard<'a, T> {
+        cell.read().unwrap()
+    }
+
+    fn try_read<'a, T: 'a>(cell: &'a RwLock<T>) -> Result<RwLockReadGuard<'a, T>, Error> {
+        cell.read().map_err(|_| Error::Poisoned)
+    }
+
+    fn clear_poison<T>(cell: &RwLock<T>) {
+        cell.clear_poison();
+    }
+
+    fn get_mut<T>(cell: &mut RwLock<T>) -> &mut T {
+        cell.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn into_inner<T>(cell: RwLock<T>) -> T {
+        cell.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl SharedCell for WithRwLock {
+    type RefMut<'a, T: 'a> = RwLockWriteGuard<'a, T>;
+
+    fn write<'a, T: 'a>(cell: &'a RwLock<T>) -> RwLockWriteGuard<'a, T> {
+        cell.write().unwrap()
+    }
+
+    fn try_write<'a, T: 'a>(cell: &'a RwLock<T>) -> Result<RwLockWriteGuard<'a, T>, Error> {
+        cell.write().map_err(|_| Error::Poisoned)
+    }
+}
+
+/// Policy `P` publishing the last value of the stat in an atomic, so `get` does not lock
+/// unless a modifier changed since the previous `get`.
+///
+/// Writes recompute and publish the value before releasing the lock, once a first `get` has
+/// told the cell how to compute it. Requires a raw type implementing [`AtomicRaw`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Cached<P>(PhantomData<P>);
+
+/// Cell of the [`Cached`] policy.
+pub struct CachedCell<C, T> {
+    cell: C,
+    value: AtomicU64,
+    stale: AtomicBool,
+    publish: OnceLock<fn(&T) -> u64>,
+}
+
+/// Write guard of the [`Cached`] policy, publishing the new value when dropped.
+pub struct CachedMut<'a, G, T>
+where
+    G: DerefMut<Target = T>,
+{
+    guard: G,
+    value: &'a AtomicU64,
+    stale: &'a AtomicBool,
+    publish: &'a OnceLock<fn(&T) -> u64>,
+}
+
+impl<G, T> Deref for CachedMut<'_, G, T>
+where
+    G: DerefMut<Target = T>,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<G, T> DerefMut for CachedMut<'_, G, T>
+where
+    G: DerefMut<Target = T>,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<G, T> Drop for CachedMut<'_, G, T>
+where
+    G: DerefMut<Target = T>,
+{
+    fn drop(&mut self) {
+        // Still holding the lock, so no read can publish the old value afterwards. A write
+        // interrupted by a panic leaves the value to the next `get`.
+        match self.publish.get() {
+            Some(publish) if !std::thread::panicking() => {
+                self.value.store(publish(&self.guard), Ordering::Release);
+                self.stale.store(false, Ordering::Release);
+            }
+            _ => self.stale.store(true, Ordering::Release),
+        }
+    }
+}
+
+impl<P: SharedCell> Cached<P> {
+    fn guard<'a, T: 'a>(
+        cell: &'a CachedCell<P::Cell<T>, T>,
+        guard: P::RefMut<'a, T>,
+    ) -> CachedMut<'a, P::RefMut<'a, T>, T> {
+        CachedMut {
+            guard,
+            value: &cell.value,
+            stale: &cell.stale,
+            publish: &cell.publish,
+        }
+    }
+}
+
+impl<P: SharedCell> CellPolicy for Cached<P> {
+    type Cell<T> = CachedCell<P::Cell<T>, T>;
+
+    type Ref<'a, T: 'a> = P::Ref<'a, T>;
+
+    fn new<T>(value: T) -> CachedCell<P::Cell<T>, T> {
+        CachedCell {
+            cell: P::new(value),
+            value: AtomicU64::new(0),
+            stale: AtomicBool::new(true),
+            publish: OnceLock::new(),
+        }
+    }
+
+    fn read<'a, T: 'a>(cell: &'a CachedCell<P::Cell<T>, T>) -> P::Ref<'a, T> {
+        P::read(&cell.cell)
+    }
+
+    fn try_read<'a, T: 'a>(cell: &'a CachedCell<P::Cell<T>, T>) -> Result<P::Ref<'a, T>, Error> {
+        P::try_read(&cell.cell)
+    }
+
+    fn clear_poison<T>(cell: &CachedCell<P::Cell<T>, T>) {
+        P::clear_poison(&cell.cell);
+        cell.stale.store(true, Ordering::Release);
+    }
+
+    fn get_mut<T>(cell: &mut CachedCell<P::Cell<T>, T>) -> &mut T {
+        *cell.stale.get_mut() = true;
+        P::get_mut(&mut cell.cell)
+    }
+
+    fn into_inner<T>(cell: CachedCell<P::Cell<T>, T>) -> T {
+        P::into_inner(cell.cell)
+    }
+}
+
+impl<P: SharedCell> SharedCell for Cached<P> {
+    type RefMut<'a, T: 'a> = CachedMut<'a, P::RefMut<'a, T>, T>;
+
+    fn write<'a, T: 'a>(cell: &'a CachedCell<P::Cell<T>, T>) -> CachedMut<'a, P::RefMut<'a, T>, T> {
+        Self::guard(cell, P::write(&cell.cell))
+    }
+
+    fn try_write<'a, T: 'a>(
+        cell: &'a CachedCell<P::Cell<T>, T>,
+    ) -> Result<CachedMut<'a, P::RefMut<'a, T>, T>, Error> {
+        Ok(Self::guard(cell, P::try_write(&cell.cell)?))
+    }
+}
+
+impl<P: SharedCell, R: AtomicRaw> ValueCell<R> for Cached<P> {
+    fn value<T>(cell: &CachedCell<P::Cell<T>, T>) -> R
+    where
+        T: StatAccess,
+        T::Marker: StatMarker<Raw = R>,
+    {
+        if !cell.stale.load(Ordering::Acquire) {
+            return R::from_u64(cell.value.load(Ordering::Acquire));
+        }
+
+        cell.publish.get_or_init(|| |stat: &T| stat.get().to_u64());
+        let stat = P::read(&cell.cell);
+        let value = stat.get();
+        cell.value.store(value.to_u64(), Ordering::Release);
+        cell.stale.store(false, Ordering::Release);
+        value
+    }
+}
+
+macro_rules! impl_value_cell {
+    ($($policy:ty),*) => {
+        $(
+            impl<R> ValueCell<R> for $policy {
+                fn value<T>(cell: &Self::Cell<T>) -> R
+                where
+                    T: StatAccess,
+                    T::Marker: StatMarker<Raw = R>,
+                {
+                    Self::read(cell).get()
+                }
+            }
+        )*
+    };
+}
+
+impl_value_cell!(Plain, WithRefCell, WithMutex, WithRwLock);
+
+#[cfg(feature = "parking_lot")]
+impl_value_cell!(WithParkingMutex, WithParkingRwLock);
+
+#[cfg(feature = "parking_lot")]
+pub use self::parking::{WithParkingMutex, WithParkingRwLock};
+
+#[cfg(feature = "parking_lot")]
+mod parking {
+    use parking_lot::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+    use super::{CellPolicy, SharedCell};
+
+    /// Stat behind a `parking_lot::Mutex`, which cannot be poisoned.
+    #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+    pub struct WithParkingMutex;
+
+    /// Stat behind a `parking_lot::RwLock`, which cannot be poisoned.
+    #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+    pub struct WithParkingRwLock;
+
+    impl CellPolicy for WithParkingMutex {
+        type Cell<T> = Mutex<T>;
+
+        type Ref<'a, T: 'a> = MutexGuard<'a, T>;
+
+        fn new<T>(value: T) -> Mutex<T> {
+            Mutex::new(value)
+        }
+
+        fn read<'a, T: 'a>(cell: &'a Mutex<T>) -> MutexGuard<'a, T> {
+            cell.lock()
+        }
+
+        fn get_mut<T>(cell: &mut Mutex<T>) -> &mut T {
+            cell.get_mut()
+        }
+
+        fn into_inner<T>(cell: Mutex<T>) -> T {
+            cell.into_inner()
+        }
+    }
+
+    impl SharedCell for WithParkingMutex {
+        type RefMut<'a, T: 'a> = MutexGuard<'a, T>;
+
+        fn write<'a, T: 'a>(cell: &'a Mutex<T>) -> MutexGuard<'a, T> {
+            cell.lock()
+        }
+    }
+
+    impl CellPolicy for WithParkingRwLock {
+        type Cell<T> = RwLock<T>;
+
+        type Ref<'a, T: 'a> = RwLockReadGuard<'a, T>;
+
+        fn new<T>(value: T) -> RwLock<T> {
+            RwLock::new(value)
+        }
+
+        fn read<'a, T: 'a>(cell: &'a RwLock<T>) -> RwLockReadGuard<'a, T> {
+            cell.read()
+        }
+
+        fn get_mut<T>(cell: &mut RwLock<T>) -> &mut T {
+            cell.get_mut()
+        }
+
+        fn into_inner<T>(cell: RwLock<T>) -> T {
+            cell.into_inner()
+        }
+    }
+
+    impl SharedCell for WithParkingRwLock {
+        type RefMut<'a, T: 'a> = RwLockWriteGuard<'a, T>;
+
+        fn write<'a, T: 'a>(cell: &'a RwLock<T>) -> RwLockWriteGuard<'a, T> {
+            cell.write()
+        }
+    }
+}
+
+/// [`Stat`] stored according to the cell policy `P`.
+///
+/// [`StatS`](crate::send::StatS) and [`StatNS`](crate::non_send::StatNS) name the `Mutex`
+/// and `RefCell` policies.
+pub struct StatCell<Marker, Layers, F, P>(pub P::Cell<Stat<Marker, Layers, F>>)
+where
+    Marker: StatMarker,
+    P: CellPolicy;
+
+impl<Marker, Layers, G, P> StatCell<Marker, Layers, Formula<G>, P>
+where
+    Marker: StatMarker,
+    Layers: LayerList,
+    G: ?Sized,
+    Formula<G>: StatFormula<Marker::Raw, Layers>,
+    P: CellPolicy,
+{
+    pub fn new(base: Marker::Raw, f: Box<G>) -> Self {
+        Self(P::new(Stat::new(base, f)))
+    }
+}
+
+impl<Marker, Layers, F, P> StatCell<Marker, Layers, F, P>
+where
+    Marker: StatMarker,
+    Layers: LayerList,
+    F: StatFormula<Marker::Raw, Layers>,
+    P: CellPolicy,
+{
+    pub fn from_formula(base: Marker::Raw, f: F) -> Self {
+        Self(P::new(Stat::from_formula(base, f)))
+    }
+
+    pub fn has<M, I>(&self, value: M) -> bool
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        P::read(&self.0).has(value)
+    }
+
+    pub fn try_has<M, I>(&self, value: M) -> Result<bool, Error>
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        Ok(P::try_read(&self.0)?.has(value))
+    }
+
+    /// Calls `f` on every modifier of the layer of `M`, holding a read for the whole loop.
+    pub fn for_each<M, I>(&self, f: impl FnMut(&M))
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        P::read(&self.0).layer::<M, I>().iter().for_each(f);
+    }
+
+    pub fn try_for_each<M, I>(&self, f: impl FnMut(&M)) -> Result<(), Error>
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        P::try_read(&self.0)?.layer::<M, I>().iter().for_each(f);
+        Ok(())
+    }
+
+    pub fn base(&self) -> Marker::Raw {
+        P::read(&self.0).base()
+    }
+
+    pub fn try_base(&self) -> Result<Marker::Raw, Error> {
+        Ok(P::try_read(&self.0)?.base())
+    }
+
+    pub fn get(&self) -> Marker::Raw
+    where
+        P: ValueCell<Marker::Raw>,
+    {
+        P::value(&self.0)
+    }
+
+    /// Like [`get`](Self::get), but also fails instead of panicking when the stat cannot be
+    /// read.
+    pub fn try_get(&self) -> Result<Marker::Raw, Error>
+    where
+        Marker::Raw: Finite,
+        Layers: FiniteLayers,
+    {
+        P::try_read(&self.0)?.try_get()
+    }
+
+    /// Accepts the modifiers left by a thread that panicked while holding the lock.
+    pub fn clear_poison(&self) {
+        P::clear_poison(&self.0);
+    }
+
+    pub fn get_mut(&mut self) -> &mut Stat<Marker, Layers, F> {
+        P::get_mut(&mut self.0)
+    }
+
+    pub fn into_inner(self) -> Stat<Marker, Layers, F> {
+        P::into_inner(self.0)
+    }
+
+    /// Like `clone`, but fails instead of panicking when the stat cannot be read.
+    pub fn try_clone(&self) -> Result<Self, Error>
+    where
+        Layers: Clone,
+        F: Clone,
+    {
+        Ok(Self(P::new(P::try_read(&self.0)?.clone())))
+    }
+}
+
+impl<Marker, Layers, F, P> StatCell<Marker, Layers, F, P>
+where
+    Marker: StatMarker,
+    Layers: LayerList,
+    F: StatFormula<Marker::Raw, Layers>,
+    P: SharedCell,
+{
+    pub fn apply<M, I>(&self, value: M)
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        P::write(&self.0).apply(value);
+    }
+
+    pub fn remove<M, I>(&self, value: M)
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        P::write(&self.0).remove(value);
+    }
+
+    /// Keeps only the modifiers of type `M` for which `f` returns `true`.
+    pub fn retain<M, I>(&self, f: impl FnMut(&mut M) -> bool)
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        P::write(&self.0).retain(f);
+    }
+
+    pub fn clear(&self) {
+        P::write(&self.0).clear();
+    }
+
+    pub fn set_base(&self, base: Marker::Raw) {
+        P::write(&self.0).set_base(base);
+    }
+
+    pub fn try_retain<M, I>(&self, f: impl FnMut(&mut M) -> bool) -> Result<(), Error>
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        P::try_write(&self.0)?.retain(f);
+        Ok(())
+    }
+
+    pub fn try_clear(&self) -> Result<(), Error> {
+        P::try_write(&self.0)?.clear();
+        Ok(())
+    }
+
+    pub fn try_set_base(&self, base: Marker::Raw) -> Result<(), Error> {
+        P::try_write(&self.0)?.set_base(base);
+        Ok(())
+    }
+
+    pub fn try_apply<M, I>(&self, value: M) -> Result<(), Error>
+    where
+        M: Modifier,
+        <M::Target as StatMarker>::Raw: Finite,
+        Layers: Selector<M, I>,
+    {
+        P::try_write(&self.0)?.try_apply(value)
+    }
+
+    pub fn try_remove<M, I>(&self, value: M) -> Result<(), Error>
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        P::try_write(&self.0)?.try_remove(value)
+    }
+
+    /// Locks the stat for several changes, seen by other threads all at once when the guard
+    /// is dropped.
+    pub fn write(&self) -> P::RefMut<'_, Stat<Marker, Layers, F>> {
+        P::write(&self.0)
+    }
+
+    /// Runs `f` on the stat under a single lock; a [`Cached`] value is recomputed once
+    /// afterwards rather than after every change.
+    pub fn edit<R>(&self, f: impl FnOnce(&mut Stat<Marker, Layers, F>) -> R) -> R {
+        f(&mut P::write(&self.0))
+    }
+
+    pub fn try_edit<R>(
+        &self,
+        f: impl FnOnce(&mut Stat<Marker, Layers, F>) -> R,
+    ) -> Result<R, Error> {
+        Ok(f(&mut *P::try_write(&self.0)?))
+    }
+}
+
+macro_rules! impl_layer_access {
+    ([$($h:ident),*], $m:ident, $apply:ident, $try_apply:ident, $remove:ident, $try_remove:ident, $has:ident, $try_has:ident, $get:ident, $for_each:ident, $try_for_each:ident) => {
+        impl<Marker, $($h,)* $m, T, const N: usize, F, P> StatCell<Marker, prefixed!([$($h),*], HCons<Layer<$m, N>, T>), F, P>
+        where
+            Marker: StatMarker,
+            $m: Modifier,
+            P: CellPolicy,
+        {
+            pub fn $has(&self, value: $m) -> bool {
+                P::read(&self.0).$has(value)
+            }
+
+            pub fn $try_has(&self, value: $m) -> Result<bool, Error> {
+                Ok(P::try_read(&self.0)?.$has(value))
+            }
+
+            pub fn $for_each(&self, f: impl FnMut(&$m)) {
+                P::read(&self.0).$get().iter().for_each(f);
+            }
+
+            pub fn $try_for_each(&self, f: impl FnMut(&$m)) -> Result<(), Error> {
+                P::try_read(&self.0)?.$get().iter().for_each(f);
+                Ok(())
+            }
+        }
+
+        impl<Marker, $($h,)* $m, T, const N: usize, F, P> StatCell<Marker, prefixed!([$($h),*], HCons<Layer<$m, N>, T>), F, P>
+        where
+            Marker: StatMarker,
+            $m: Modifier,
+            P: SharedCell,
+        {
+            pub fn $apply(&self, value: $m) {
+                P::write(&self.0).$apply(value);
+            }
+
+            pub fn $remove(&self, value: $m) {
+                P::write(&self.0).$remove(value);
+            }
+
+            pub fn $try_apply(&self, value: $m) -> Result<(), Error>
+            where
+                <$m::Target as StatMarker>::Raw: Finite,
+            {
+                P::try_write(&self.0)?.$try_apply(value)
+            }
+
+            pub fn $try_remove(&self, value: $m) -> Result<(), Error> {
+                P::try_write(&self.0)?.$try_remove(value)
+            }
+        }
+    };
+}
+
+impl_layer_access!(
+    [],
+    M1,
+    apply_m1,
+    try_apply_m1,
+    remove_m1,
+    try_remove_m1,
+    has_m1,
+    try_has_m1,
+    m1,
+    for_each_m1,
+    try_for_each_m1
+);
+impl_layer_access!(
+    [H1],
+    M2,
+    apply_m2,
+    try_apply_m2,
+    remove_m2,
+    try_remove_m2,
+    has_m2,
+    try_has_m2,
+    m2,
+    for_each_m2,
+    try_for_each_m2
+);
+impl_layer_access!(
+    [H1, H2],
+    M3,
+    apply_m3,
+    try_apply_m3,
+    remove_m3,
+    try_remove_m3,
+    has_m3,
+    try_has_m3,
+    m3,
+    for_each_m3,
+    try_for_each_m3
+);
+impl_layer_access!(
+    [H1, H2, H3],
+    M4,
+    apply_m4,
+    try_apply_m4,
+    remove_m4,
+    try_remove_m4,
+    has_m4,
+    try_has_m4,
+    m4,
+    for_each_m4,
+    try_for_each_m4
+);
+impl_layer_access!(
+    [H1, H2, H3, H4],
+    M5,
+    apply_m5,
+    try_apply_m5,
+    remove_m5,
+    try_remove_m5,
+    has_m5,
+    try_has_m5,
+    m5,
+    for_each_m5,
+    try_for_each_m5
+);
+impl_layer_access!(
+    [H1, H2, H3, H4, H5],
+    M6,
+    apply_m6,
+    try_apply_m6,
+    remove_m6,
+    try_remove_m6,
+    has_m6,
+    try_has_m6,
+    m6,
+    for_each_m6,
+    try_for_each_m6
+);
+impl_layer_access!(
+    [H1, H2, H3, H4, H5, H6],
+    M7,
+    apply_m7,
+    try_apply_m7,
+    remove_m7,
+    try_remove_m7,
+    has_m7,
+    try_has_m7,
+    m7,
+    for_each_m7,
+    try_for_each_m7
+);
+impl_layer_access!(
+    [H1, H2, H3, H4, H5, H6, H7],
+    M8,
+    apply_m8,
+    try_apply_m8,
+    remove_m8,
+    try_remove_m8,
+    has_m8,
+    try_has_m8,
+    m8,
+    for_each_m8,
+    try_for_each_m8
+);
+
+impl<Marker, Layers, F, P> Clone for StatCell<Marker, Layers, F, P>
+where
+    Marker: StatMarker,
+    Layers: Clone,
+    F: Clone,
+    P: CellPolicy,
+{
+    fn clone(&self) -> Self {
+        Self(P::new(P::read(&self.0).clone()))
+    }
+}
+
+impl<Marker, Layers, F, P> fmt::Debug for StatCell<Marker, Layers, F, P>
+where
+    Marker: StatMarker,
+    Stat<Marker, Layers, F>: fmt::Debug,
+    P: CellPolicy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match P::try_read(&self.0) {
+            Ok(stat) => f.debug_tuple("StatCell").field(&*stat).finish(),
+            Err(e) => f
+                .debug_tuple("StatCell")
+                .field(&format_args!("<{e}>"))
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        access::StatAccess,
+        layers,
+        modifier::standard::{Flat, Multiplicative},
+    };
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct DummyMarker;
+
+    impl StatMarker for DummyMarker {
+        type Raw = f32;
+    }
+
+    type Flat32 = Flat<DummyMarker, f32, ()>;
+    type More32 = Multiplicative<DummyMarker, f32, ()>;
+    type Layers = layers![Flat32, More32];
+
+    fn formula(base: f32, flat: &Layer<Flat32>, more: &Layer<More32>) -> f32 {
+        (base + Flat32::combine(flat)) * More32::combine(more)
+    }
+
+    fn shared<P: SharedCell + ValueCell<f32>>() -> f32 {
+        let stat = StatCell::<DummyMarker, Layers, _, P>::from_formula(1., formula);
+        stat.apply_m1(Flat::from_raw(2.));
+        stat.apply(Multiplicative::from_raw(2.));
+        stat.clone().get()
+    }
+
+    #[test]
+    fn test_policies() {
+        assert_eq!(6., shared::<WithRefCell>());
+        assert_eq!(6., shared::<WithMutex>());
+        assert_eq!(6., shared::<WithRwLock>());
+        assert_eq!(6., shared::<Cached<WithMutex>>());
+        #[cfg(feature = "parking_lot")]
+        {
+            assert_eq!(6., shared::<WithParkingMutex>());
+            assert_eq!(6., shared::<WithParkingRwLock>());
+        }
+
+        let mut plain = StatCell::<DummyMarker, Layers, _, Plain>::from_formula(1., formula);
+        plain.get_mut().apply_m1(Flat::from_raw(2.));
+        StatAccess::apply(&mut plain, Multiplicative::from_raw(2.));
+
+        assert_eq!(6., plain.get());
+        assert!(plain.has_m2(Multiplicative::from_raw(2.)));
+
+        plain.get_mut().remove_m1(Flat::from_raw(2.));
+
+        assert_eq!(2., plain.into_inner().get());
+    }
+
+    #[test]
+    fn test_cached() {
+        let stat = StatCell::<DummyMarker, Layers, _, Cached<WithMutex>>::from_formula(1., formula);
+        stat.apply_m1(Flat::from_raw(2.));
+
+        assert_eq!(3., stat.get());
+
+        // A published value is read without taking the lock.
+        let guard = stat.0.cell.lock().unwrap();
+        assert_eq!(3., stat.get());
+        drop(guard);
+
+        // Writes publish the new value before releasing the lock.
+        stat.apply_m2(Multiplicative::from_raw(2.));
+        let guard = stat.0.cell.lock().unwrap();
+
+        assert_eq!(6., stat.get());
+
+        drop(guard);
+    }
+
+    #[test]
+    fn test_errors() {
+        let local = StatCell::<DummyMarker, Layers, _, WithRefCell>::from_formula(1., formula);
+        let borrow = WithRefCell::read(&local.0);
+
+        assert_eq!(
+            Err(Error::AlreadyBorrowed),
+            local.try_apply_m1(Flat::from_raw(1.))
+        );
+
+        drop(borrow);
+
+        assert_eq!(Ok(()), local.try_apply(Flat::from_raw(1.)));
+        assert_eq!(
+            Err(Error::NotFound { layer: 2 }),
+            local.try_remove_m2(Multiplicative::from_raw(2.))
+        );
+        assert_eq!(Ok(()), local.try_remove(Flat::from_raw(1.)));
+
+        local.apply_m1(Flat::from_raw(1.));
+        local.for_each_m1(|_| {
+            assert_eq!(Err(Error::AlreadyBorrowed), local.try_clear());
+            assert_eq!(Ok(true), local.try_has(Flat::from_raw(1.)));
+        });
+        local.try_clear().unwrap();
+
+        let shared = StatCell::<DummyMarker, Layers, _, WithMutex>::from_formula(1., formula);
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let _guard = WithMutex::write(&shared.0);
+                panic!("poison");
+            })
+            .join()
+            .unwrap_err();
+        });
+
+        assert_eq!(Err(Error::Poisoned), shared.try_get());
+        assert_eq!(
+            Err(Error::Poisoned),
+            shared.try_apply_m1(Flat::from_raw(1.))
+        );
+        assert_eq!(Err(Error::Poisoned), shared.try_has_m1(Flat::from_raw(1.)));
+        assert_eq!(Err(Error::Poisoned), shared.try_for_each_m2(|_| {}));
+        assert_eq!(Err(Error::Poisoned), shared.try_base());
+        assert_eq!(Err(Error::Poisoned), shared.try_set_base(2.));
+        assert_eq!(
+            Err(Error::Poisoned),
+            shared.try_retain(|_: &mut Flat32| true)
+        );
+        assert_eq!(Err(Error::Poisoned), shared.try_clear());
+        assert_eq!(Err(Error::Poisoned), shared.try_clone().map(|_| ()));
+        assert_eq!(
+            "StatCell(<stat lock poisoned by a panicking thread>)",
+            format!("{shared:?}")
+        );
+
+        shared.clear_poison();
+
+        assert_eq!(Ok(()), shared.try_apply_m1(Flat::from_raw(1.)));
+        assert_eq!(Ok(2.), shared.try_get());
+
+        let mut poisoned =
+            StatCell::<DummyMarker, Layers, _, WithRwLock>::from_formula(1., formula);
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let _guard = WithRwLock::write(&poisoned.0);
+                panic!("poison");
+            })
+            .join()
+            .unwrap_err();
+        });
+
+        // Exclusive access needs no lock, so the poison is ignored.
+        poisoned.get_mut().apply_m1(Flat::from_raw(1.));
+        assert_eq!(2., poisoned.into_inner().get());
+    }
+
+    #[test]
+    fn test_edit() {
+        let stat =
+            StatCell::<DummyMarker, Layers, _, Cached<WithRwLock>>::from_formula(1., formula);
+        assert_eq!(1., stat.get());
+
+        let count = stat.edit(|stat| {
+            stat.apply_m1(Flat::from_raw(1.));
+            stat.apply_m1(Flat::from_raw(2.));
+            stat.apply_m2(Multiplicative::from_raw(2.));
+            stat.m1().len()
+        });
+
+        assert_eq!(2, count);
+        assert_eq!(8., stat.get());
+
+        let mut guard = stat.write();
+        guard.remove_m1(Flat::from_raw(2.));
+        guard.remove_m2(Multiplicative::from_raw(2.));
+        drop(guard);
+
+        assert_eq!(2., stat.get());
+        assert_eq!(Ok(2.), stat.try_edit(|stat| stat.get()));
+    }
+
+    #[test]
+    fn test_shared() {
+        let stat = Arc::new(StatCell::<DummyMarker, Layers, _, WithMutex>::from_formula(
+            1., formula,
+        ));
+        let other = Arc::clone(&stat);
+        other.apply_m1(Flat::from_raw(1.));
+        other.apply(Flat::from_raw(2.));
+        other.apply_m2(Multiplicative::from_raw(2.));
+
+        let mut flats = Vec::new();
+        stat.for_each_m1(|m| flats.push(m.raw()));
+        let mut count = 0;
+        stat.for_each(|_: &Multiplicative<DummyMarker, f32, ()>| count += 1);
+
+        assert_eq!((vec![1., 2.], 1), (flats, count));
+
+        stat.remove_m1(Flat::from_raw(1.));
+        stat.remove(Multiplicative::from_raw(2.));
+
+        assert_eq!(3., other.get());
+
+        stat.set_base(4.);
+
+        assert_eq!(6., other.get());
+
+        stat.retain(|m: &mut Flat32| m.raw() > 2.);
+
+        assert_eq!(4., other.get());
+
+        other.apply_m1(Flat::from_raw(1.));
+        other.clear();
+
+        assert_eq!((4., false), (stat.get(), stat.has_m1(Flat::from_raw(1.))));
+    }
+}
@@ -0,0 +1,719 @@
+use std::{
+    fmt,
+    ops::{Add, Mul},
+};
+
+use crate::{
+    block::{Breakdown, DynStat},
+    cell::{CellPolicy, SharedCell, ValueCell},
+    error::Error,
+    modifier::{
+        standard::{
+            Additive, Flat, Multiplicative, PostAdditive, PostMultiplicative, StandardFormula,
+        },
+        Modifier,
+    },
+    raw::Finite,
+    stat::{Stat5, StatMarker},
+};
+
+/// Stat made of the five standard layers and combined by [`StandardFormula`], stored
+/// according to the cell policy `P`.
+pub struct StandardStat<Marker, Metadata, P, const N: usize = 2>(
+    pub P::Cell<
+        Stat5<
+            Marker,
+            Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
+            Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+            PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+            Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+            PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+            N,
+            StandardFormula,
+        >,
+    >,
+)
+where
+    P: CellPolicy,
+    Marker: StatMarker,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Metadata: PartialEq + Clone + Copy,
+    <Marker as StatMarker>::Raw:
+        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >;
+
+impl<Marker, Metadata, P, const N: usize> StandardStat<Marker, Metadata, P, N>
+where
+    P: CellPolicy,
+    Marker: StatMarker,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Metadata: PartialEq + Clone + Copy,
+    <Marker as StatMarker>::Raw:
+        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >
+{
+    pub fn new(base: Marker::Raw) -> Self {
+        Self(P::new(Stat5::from_formula(base, StandardFormula)))
+    }
+
+    pub fn has_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
+        P::read(&self.0).has_m1(value)
+    }
+
+    pub fn has_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
+        P::read(&self.0).has_m2(value)
+    }
+
+    pub fn has_post_add(&self, value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
+        P::read(&self.0).has_m3(value)
+    }
+
+    pub fn has_multiplicative(&self, value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
+        P::read(&self.0).has_m4(value)
+    }
+
+    pub fn has_post_mul(&self, value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
+        P::read(&self.0).has_m5(value)
+    }
+
+    pub fn try_has_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<bool, Error> {
+        Ok(P::try_read(&self.0)?.has_m1(value))
+    }
+
+    pub fn try_has_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<bool, Error> {
+        Ok(P::try_read(&self.0)?.has_m2(value))
+    }
+
+    pub fn try_has_post_add(&self, value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<bool, Error> {
+        Ok(P::try_read(&self.0)?.has_m3(value))
+    }
+
+    pub fn try_has_multiplicative(&self, value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<bool, Error> {
+        Ok(P::try_read(&self.0)?.has_m4(value))
+    }
+
+    pub fn try_has_post_mul(&self, value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<bool, Error> {
+        Ok(P::try_read(&self.0)?.has_m5(value))
+    }
+
+    pub fn for_each_flat<F>(&self, f: F)
+    where
+        F: FnMut(&Flat<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::read(&self.0).m1().iter().for_each(f);
+    }
+
+    pub fn for_each_additive<F>(&self, f: F)
+    where
+        F: FnMut(&Additive<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::read(&self.0).m2().iter().for_each(f);
+    }
+
+    pub fn for_each_post_add<F>(&self, f: F)
+    where
+        F: FnMut(&PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::read(&self.0).m3().iter().for_each(f);
+    }
+
+    pub fn for_each_multiplicative<F>(&self, f: F)
+    where
+        F: FnMut(&Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::read(&self.0).m4().iter().for_each(f);
+    }
+
+    pub fn for_each_post_mul<F>(&self, f: F)
+    where
+        F: FnMut(&PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::read(&self.0).m5().iter().for_each(f);
+    }
+
+    pub fn try_for_each_flat<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&Flat<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::try_read(&self.0)?.m1().iter().for_each(f);
+        Ok(())
+    }
+
+    pub fn try_for_each_additive<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&Additive<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::try_read(&self.0)?.m2().iter().for_each(f);
+        Ok(())
+    }
+
+    pub fn try_for_each_post_add<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::try_read(&self.0)?.m3().iter().for_each(f);
+        Ok(())
+    }
+
+    pub fn try_for_each_multiplicative<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::try_read(&self.0)?.m4().iter().for_each(f);
+        Ok(())
+    }
+
+    pub fn try_for_each_post_mul<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>),
+    {
+        P::try_read(&self.0)?.m5().iter().for_each(f);
+        Ok(())
+    }
+
+    pub fn breakdown(&self) -> Breakdown<Marker::Raw> {
+        Self::breakdown_of(&P::read(&self.0))
+    }
+
+    pub fn try_breakdown(&self) -> Result<Breakdown<Marker::Raw>, Error> {
+        Ok(Self::breakdown_of(&*P::try_read(&self.0)?))
+    }
+
+    fn breakdown_of(stat: &Stat5<
+        Marker,
+        Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        N,
+        StandardFormula,
+    >) -> Breakdown<Marker::Raw> {
+        Breakdown {
+            name: Marker::name(),
+            formula: None,
+            base: stat.base(),
+            flat: Flat::combine(stat.m1()),
+            additive: Additive::combine(stat.m2()),
+            post_add: PostAdditive::combine(stat.m3()),
+            multiplicative: Multiplicative::combine(stat.m4()),
+            post_mul: PostMultiplicative::combine(stat.m5()),
+            value: stat.get(),
+        }
+    }
+
+    pub fn base(&self) -> Marker::Raw {
+        P::read(&self.0).base()
+    }
+
+    pub fn try_base(&self) -> Result<Marker::Raw, Error> {
+        Ok(P::try_read(&self.0)?.base())
+    }
+
+    pub fn get(&self) -> Marker::Raw
+    where
+        P: ValueCell<Marker::Raw>,
+    {
+        P::value(&self.0)
+    }
+
+    pub fn try_get(&self) -> Result<Marker::Raw, Error>
+    where
+        Marker::Raw: Finite,
+    {
+        P::try_read(&self.0)?.try_get()
+    }
+
+    /// Accepts the modifiers left by a thread that panicked while holding the lock.
+    pub fn clear_poison(&self) {
+        P::clear_poison(&self.0);
+    }
+
+    pub fn get_mut(&mut self) -> &mut Stat5<
+        Marker,
+        Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        N,
+        StandardFormula,
+    > {
+        P::get_mut(&mut self.0)
+    }
+
+    /// Like `clone`, but fails instead of panicking when the stat cannot be read.
+    pub fn try_clone(&self) -> Result<Self, Error> {
+        Ok(Self(P::new(P::try_read(&self.0)?.clone())))
+    }
+}
+
+impl<Marker, Metadata, P, const N: usize> StandardStat<Marker, Metadata, P, N>
+where
+    P: SharedCell,
+    Marker: StatMarker,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Metadata: PartialEq + Clone + Copy,
+    <Marker as StatMarker>::Raw:
+        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >
+{
+    pub fn apply_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).apply_m1(value);
+    }
+
+    pub fn apply_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).apply_m2(value);
+    }
+
+    pub fn apply_post_add(&self, value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).apply_m3(value);
+    }
+
+    pub fn apply_multiplicative(&self, value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).apply_m4(value);
+    }
+
+    pub fn apply_post_mul(&self, value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).apply_m5(value);
+    }
+
+    pub fn try_apply_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error>
+    where
+        Marker::Raw: Finite,
+    {
+        P::try_write(&self.0)?.try_apply_m1(value)
+    }
+
+    pub fn try_apply_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error>
+    where
+        Marker::Raw: Finite,
+    {
+        P::try_write(&self.0)?.try_apply_m2(value)
+    }
+
+    pub fn try_apply_post_add(&self, value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error>
+    where
+        Marker::Raw: Finite,
+    {
+        P::try_write(&self.0)?.try_apply_m3(value)
+    }
+
+    pub fn try_apply_multiplicative(&self, value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error>
+    where
+        Marker::Raw: Finite,
+    {
+        P::try_write(&self.0)?.try_apply_m4(value)
+    }
+
+    pub fn try_apply_post_mul(&self, value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error>
+    where
+        Marker::Raw: Finite,
+    {
+        P::try_write(&self.0)?.try_apply_m5(value)
+    }
+
+    pub fn remove_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).remove_m1(value);
+    }
+
+    pub fn remove_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).remove_m2(value);
+    }
+
+    pub fn remove_post_add(&self, value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).remove_m3(value);
+    }
+
+    pub fn remove_multiplicative(&self, value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).remove_m4(value);
+    }
+
+    pub fn remove_post_mul(&self, value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) {
+        P::write(&self.0).remove_m5(value);
+    }
+
+    pub fn try_remove_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error> {
+        P::try_write(&self.0)?.try_remove_m1(value)
+    }
+
+    pub fn try_remove_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error> {
+        P::try_write(&self.0)?.try_remove_m2(value)
+    }
+
+    pub fn try_remove_post_add(&self, value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error> {
+        P::try_write(&self.0)?.try_remove_m3(value)
+    }
+
+    pub fn try_remove_multiplicative(&self, value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error> {
+        P::try_write(&self.0)?.try_remove_m4(value)
+    }
+
+    pub fn try_remove_post_mul(&self, value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> Result<(), Error> {
+        P::try_write(&self.0)?.try_remove_m5(value)
+    }
+
+    /// Locks the stat for several changes, seen by other threads all at once when the guard
+    /// is dropped.
+    pub fn write(&self) -> P::RefMut<'_, Stat5<
+        Marker,
+        Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        N,
+        StandardFormula,
+    >> {
+        P::write(&self.0)
+    }
+
+    /// Runs `f` on the stat under a single lock, e.g. to apply every modifier of an item;
+    /// a cached value is recomputed once afterwards rather than after every change.
+    pub fn edit<R>(&self, f: impl FnOnce(&mut Stat5<
+        Marker,
+        Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        N,
+        StandardFormula,
+    >) -> R) -> R {
+        f(&mut P::write(&self.0))
+    }
+
+    pub fn try_edit<R>(&self, f: impl FnOnce(&mut Stat5<
+        Marker,
+        Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        N,
+        StandardFormula,
+    >) -> R) -> Result<R, Error> {
+        Ok(f(&mut *P::try_write(&self.0)?))
+    }
+
+    pub fn clear(&self) {
+        P::write(&self.0).clear();
+    }
+
+    pub fn try_clear(&self) -> Result<(), Error> {
+        P::try_write(&self.0)?.clear();
+        Ok(())
+    }
+
+    pub fn set_base(&self, base: Marker::Raw) {
+        P::write(&self.0).set_base(base);
+    }
+
+    pub fn try_set_base(&self, base: Marker::Raw) -> Result<(), Error> {
+        P::try_write(&self.0)?.set_base(base);
+        Ok(())
+    }
+
+    /// Removes every modifier carrying `source` as metadata.
+    pub fn clear_source(&self, source: Metadata) {
+        Self::clear_source_of(&mut P::write(&self.0), source);
+    }
+
+    pub fn try_clear_source(&self, source: Metadata) -> Result<(), Error> {
+        Self::clear_source_of(&mut *P::try_write(&self.0)?, source);
+        Ok(())
+    }
+
+    fn clear_source_of(stat: &mut Stat5<
+        Marker,
+        Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+        N,
+        StandardFormula,
+    >, source: Metadata) {
+        stat.retain(|m: &mut Flat<Marker, <Marker as StatMarker>::Raw, Metadata>| m.metadata() != Some(source));
+        stat.retain(|m: &mut Additive<Marker, <Marker as StatMarker>::Raw, Metadata>| m.metadata() != Some(source));
+        stat.retain(|m: &mut PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>| m.metadata() != Some(source));
+        stat.retain(|m: &mut Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>| m.metadata() != Some(source));
+        stat.retain(|m: &mut PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>| m.metadata() != Some(source));
+    }
+}
+
+impl<Marker, Metadata, P, const N: usize> Clone for StandardStat<Marker, Metadata, P, N>
+where
+    P: CellPolicy,
+    Marker: StatMarker,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Metadata: PartialEq + Clone + Copy,
+    <Marker as StatMarker>::Raw:
+        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >
+{
+    fn clone(&self) -> Self {
+        Self(P::new(P::read(&self.0).clone()))
+    }
+}
+
+impl<Marker, Metadata, P, const N: usize> Default for StandardStat<Marker, Metadata, P, N>
+where
+    P: CellPolicy,
+    Marker: StatMarker,
+    Marker::Raw: Default,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Metadata: PartialEq + Clone + Copy,
+    <Marker as StatMarker>::Raw:
+        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >
+{
+    fn default() -> Self {
+        Self::new(Marker::default_base().unwrap_or_default())
+    }
+}
+
+impl<Marker, Metadata, P, const N: usize> fmt::Debug for StandardStat<Marker, Metadata, P, N>
+where
+    P: CellPolicy,
+    Marker: StatMarker,
+    Marker::Raw: fmt::Debug,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Metadata: PartialEq + Clone + Copy + fmt::Debug,
+    <Marker as StatMarker>::Raw:
+        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stat = match P::try_read(&self.0) {
+            Ok(stat) => stat,
+            Err(e) => return f.debug_tuple("StandardStat").field(&format_args!("<{e}>")).finish(),
+        };
+        f.debug_struct("StandardStat")
+            .field("base", &stat.base())
+            .field("flat", stat.m1())
+            .field("additive", stat.m2())
+            .field("post_add", stat.m3())
+            .field("multiplicative", stat.m4())
+            .field("post_mul", stat.m5())
+            .field("value", &stat.get())
+            .finish()
+    }
+}
+
+impl<Marker, Metadata, P, const N: usize> fmt::Display for StandardStat<Marker, Metadata, P, N>
+where
+    P: CellPolicy,
+    Marker: StatMarker,
+    Marker::Raw: fmt::Display,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Metadata: PartialEq + Clone + Copy,
+    <Marker as StatMarker>::Raw:
+        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.try_breakdown() {
+            Ok(breakdown) => breakdown.write_formula(f),
+            Err(e) => write!(f, "<{e}>"),
+        }
+    }
+}
+
+impl<Marker, Metadata, P, const N: usize> DynStat<Marker::Raw, Metadata> for StandardStat<Marker, Metadata, P, N>
+where
+    P: SharedCell + ValueCell<Marker::Raw>,
+    Marker: StatMarker,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Metadata: PartialEq + Clone + Copy,
+    <Marker as StatMarker>::Raw:
+        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Mul<
+        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >,
+    <Marker as StatMarker>::Raw: Add<
+        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
+        Output = <Marker as StatMarker>::Raw,
+    >
+{
+    fn from_base(base: Option<Marker::Raw>) -> Self
+    where
+        Marker::Raw: Default,
+    {
+        Self::new(base.or_else(Marker::default_base).unwrap_or_default())
+    }
+
+    fn marker_name() -> &'static str {
+        Marker::name()
+    }
+
+    fn name(&self) -> &'static str {
+        Marker::name()
+    }
+
+    fn base(&self) -> Marker::Raw {
+        StandardStat::base(self)
+    }
+
+    fn get(&self) -> Marker::Raw {
+        StandardStat::get(self)
+    }
+
+    fn clear(&self) {
+        StandardStat::clear(self)
+    }
+
+    fn clear_source(&self, source: Metadata) {
+        StandardStat::clear_source(self, source)
+    }
+
+    fn breakdown(&self) -> Breakdown<Marker::Raw> {
+        StandardStat::breakdown(self)
+    }
+}
@@ -0,0 +1,320 @@
+/// Declares a stat type with named layers and a fixed formula.
+///
+/// ```
+/// use maxstat::{define_stat, prelude::*};
+///
+/// #[derive(PartialEq, Clone, Copy)]
+/// pub struct DamageMarker;
+///
+/// impl StatMarker for DamageMarker {
+///     type Raw = f32;
+/// }
+///
+/// #[derive(PartialEq, Clone, Copy)]
+/// pub enum Source {
+///     Sword,
+///     Ring,
+/// }
+///
+/// define_stat! {
+///     pub struct Damage: send<DamageMarker> {
+///         flat: Flat<DamageMarker, f32, Source>,
+///         increased: Additive<DamageMarker, f32, Source>,
+///         more: Multiplicative<DamageMarker, f32, Source>,
+///     }
+///     formula = |base| (base + Flat::combine(flat)) * Additive::combine(increased)
+///         * Multiplicative::combine(more);
+/// }
+///
+/// let damage = Damage::new(10.);
+/// damage.apply_flat(*Flat::from_raw(5.).set_metadata(Some(Source::Sword)));
+/// damage.apply_more(*Multiplicative::from_raw(2.).set_metadata(Some(Source::Ring)));
+///
+/// assert_eq!(30., damage.get());
+/// ```
+///
+/// The stat wraps a [`StatCell`](crate::cell::StatCell) that is `send` (behind a `Mutex`) or
+/// `non_send` (behind a `RefCell`), with an optional inline capacity per layer such as
+/// `send<DamageMarker, 4>`, and derefs to it for the `try_*` and `edit` methods. Each layer
+/// gets `apply_<layer>`, `remove_<layer>`, `has_<layer>` and `for_each_<layer>`; the formula
+/// sees every layer by name and is compiled into a zero-sized `<Name>Formula`.
+#[macro_export]
+macro_rules! define_stat {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident: $flavor:ident<$marker:ty, $n:literal> {
+            $($layer:ident: $m:ty),+ $(,)?
+        }
+        formula = |$base:ident| $formula:expr;
+    ) => {
+        $crate::define_stat!(
+            @stat [$(#[$attr])*] $vis $name $flavor $marker, $n;
+            $($layer: $m),+;
+            |$base| $formula
+        );
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident: $flavor:ident<$marker:ty> {
+            $($layer:ident: $m:ty),+ $(,)?
+        }
+        formula = |$base:ident| $formula:expr;
+    ) => {
+        $crate::define_stat!(
+            @stat [$(#[$attr])*] $vis $name $flavor $marker, 2;
+            $($layer: $m),+;
+            |$base| $formula
+        );
+    };
+
+    (
+        @stat [$($attr:tt)*] $vis:vis $name:ident $flavor:ident $marker:ty, $n:literal;
+        $($layer:ident: $m:ty),+;
+        |$base:ident| $formula:expr
+    ) => {
+        $crate::__paste! {
+            #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+            $vis struct [<$name Formula>];
+
+            impl $crate::formula::StatFormula<
+                <$marker as $crate::stat::StatMarker>::Raw,
+                $crate::layers![$($m),+; $n],
+            > for [<$name Formula>] {
+                fn eval(
+                    &self,
+                    $base: <$marker as $crate::stat::StatMarker>::Raw,
+                    layers: &$crate::layers![$($m),+; $n],
+                ) -> <$marker as $crate::stat::StatMarker>::Raw {
+                    let $crate::define_stat!(@pat $($layer),+) = layers;
+                    $formula
+                }
+            }
+
+            $($attr)*
+            $vis struct $name(
+                pub $crate::cell::StatCell<
+                    $marker,
+                    $crate::layers![$($m),+; $n],
+                    [<$name Formula>],
+                    $crate::define_stat!(@policy $flavor),
+                >,
+            );
+
+            #[allow(dead_code)]
+            impl $name {
+                pub fn new(base: <$marker as $crate::stat::StatMarker>::Raw) -> Self {
+                    Self($crate::cell::StatCell::from_formula(base, [<$name Formula>]))
+                }
+
+                pub fn base(&self) -> <$marker as $crate::stat::StatMarker>::Raw {
+                    self.0.base()
+                }
+
+                pub fn get(&self) -> <$marker as $crate::stat::StatMarker>::Raw {
+                    self.0.get()
+                }
+            }
+
+            impl ::std::ops::Deref for $name {
+                type Target = $crate::cell::StatCell<
+                    $marker,
+                    $crate::layers![$($m),+; $n],
+                    [<$name Formula>],
+                    $crate::define_stat!(@policy $flavor),
+                >;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl Clone for $name {
+                fn clone(&self) -> Self {
+                    Self(self.0.clone())
+                }
+            }
+
+            $crate::define_stat!(@access [] $name, $marker, $crate::layers![$($m),+; $n]);
+            $crate::define_stat!(@access ['a] &'a $name, $marker, $crate::layers![$($m),+; $n]);
+        }
+
+        $crate::define_stat!(@layers $name, $crate::layer::Here; $($layer: $m),+);
+    };
+
+    (@policy send) => { $crate::cell::WithMutex };
+    (@policy non_send) => { $crate::cell::WithRefCell };
+
+    (@access [$($g:tt)*] $ty:ty, $marker:ty, $layers:ty) => {
+        impl<$($g)*> $crate::access::StatAccess for $ty {
+            type Marker = $marker;
+
+            type Layers = $layers;
+
+            fn apply<M, I>(&mut self, value: M)
+            where
+                M: $crate::modifier::Modifier,
+                $layers: $crate::layer::Selector<M, I>,
+            {
+                self.0.apply(value);
+            }
+
+            fn remove<M, I>(&mut self, value: M)
+            where
+                M: $crate::modifier::Modifier,
+                $layers: $crate::layer::Selector<M, I>,
+            {
+                self.0.remove(value);
+            }
+
+            fn has<M, I>(&self, value: M) -> bool
+            where
+                M: $crate::modifier::Modifier,
+                $layers: $crate::layer::Selector<M, I>,
+            {
+                self.0.has(value)
+            }
+
+            fn for_each<M, I>(&self, f: impl FnMut(&M))
+            where
+                M: $crate::modifier::Modifier,
+                $layers: $crate::layer::Selector<M, I>,
+            {
+                self.0.for_each(f);
+            }
+
+            fn base(&self) -> <$marker as $crate::stat::StatMarker>::Raw {
+                self.0.base()
+            }
+
+            fn get(&self) -> <$marker as $crate::stat::StatMarker>::Raw {
+                self.0.get()
+            }
+        }
+    };
+
+    (@layers $name:ident, $index:ty; $layer:ident: $m:ty $(, $rest:ident: $rest_m:ty)*) => {
+        $crate::__paste! {
+            #[allow(dead_code)]
+            impl $name {
+                pub fn [<apply_ $layer>](&self, value: $m) {
+                    self.0.apply::<$m, $index>(value);
+                }
+
+                pub fn [<remove_ $layer>](&self, value: $m) {
+                    self.0.remove::<$m, $index>(value);
+                }
+
+                pub fn [<has_ $layer>](&self, value: $m) -> bool {
+                    self.0.has::<$m, $index>(value)
+                }
+
+                pub fn [<for_each_ $layer>]<F>(&self, f: F)
+                where
+                    F: FnMut(&$m),
+                {
+                    self.0.for_each::<$m, $index>(f);
+                }
+            }
+        }
+
+        $crate::define_stat!(@layers $name, $crate::layer::There<$index>; $($rest: $rest_m),*);
+    };
+    (@layers $name:ident, $index:ty;) => {};
+
+    (@pat) => { $crate::layer::HNil };
+    (@pat $layer:ident $(, $rest:ident)*) => {
+        $crate::layer::HCons { head: $layer, tail: $crate::define_stat!(@pat $($rest),*) }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        access::StatAccess,
+        error::Error,
+        modifier::{
+            standard::{Additive, Flat, Multiplicative},
+            Modifier,
+        },
+        stat::StatMarker,
+    };
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct DamageMarker;
+
+    impl StatMarker for DamageMarker {
+        type Raw = f32;
+    }
+
+    type DamageFlat = Flat<DamageMarker, f32, ()>;
+
+    define_stat! {
+        struct Damage: send<DamageMarker> {
+            flat: DamageFlat,
+            increased: Additive<DamageMarker, f32, ()>,
+            more: Multiplicative<DamageMarker, f32, ()>,
+            added: DamageFlat,
+        }
+        formula = |base| (base + Flat::combine(flat)) * Additive::combine(increased)
+            * Multiplicative::combine(more)
+            + Flat::combine(added);
+    }
+
+    define_stat! {
+        struct DamageNS: non_send<DamageMarker, 4> {
+            flat: DamageFlat,
+            more: Multiplicative<DamageMarker, f32, ()>,
+        }
+        formula = |base| (base + Flat::combine(flat)) * Multiplicative::combine(more);
+    }
+
+    #[test]
+    fn test_send() {
+        let stat = Damage::new(10.);
+
+        stat.apply_flat(Flat::from_raw(5.));
+        stat.apply_increased(Additive::from_raw(1.));
+        stat.apply_more(Multiplicative::from_raw(2.));
+        stat.apply_added(Flat::from_raw(3.));
+
+        assert_eq!(63., stat.get());
+        assert!(stat.has_flat(Flat::from_raw(5.)));
+        assert!(!stat.has_added(Flat::from_raw(5.)));
+
+        let mut total = 0.;
+        stat.for_each_added(|m| total += m.raw());
+        assert_eq!(3., total);
+
+        let copy = stat.clone();
+        stat.remove_added(Flat::from_raw(3.));
+
+        assert_eq!(60., stat.get());
+        assert_eq!(63., copy.get());
+    }
+
+    #[test]
+    fn test_non_send() {
+        let stat = DamageNS::new(1.);
+
+        stat.apply_flat(Flat::from_raw(2.));
+        stat.apply_more(Multiplicative::from_raw(2.));
+        stat.apply_more(Multiplicative::from_raw(3.));
+
+        assert_eq!(18., stat.get());
+
+        stat.remove_more(Multiplicative::from_raw(2.));
+
+        assert_eq!(9., stat.get());
+        assert_eq!(1., stat.base());
+
+        let mut values = Vec::new();
+        stat.for_each_flat(|_| values.push(stat.get()));
+
+        assert_eq!(vec![9.], values);
+        assert_eq!(
+            Err(Error::AlreadyBorrowed),
+            stat.edit(|_| stat.try_apply(Flat::from_raw(1.)))
+        );
+        assert_eq!(9., StatAccess::get(&&stat));
+    }
+}
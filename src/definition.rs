@@ -0,0 +1,1066 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs, io,
+    marker::PhantomData,
+    ops::{Div, Sub},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    access::StatAccess,
+    block::{Breakdown, DynStat},
+    cell::{CellPolicy, StatCell, WithMutex},
+    error::Error,
+    expr::{Bindings, Expression},
+    formula::{DynStatFormula, Formula, StatFormula},
+    layer::Selector,
+    modifier::{
+        standard::{
+            Additive, Flat, Multiplicative, PostAdditive, PostMultiplicative, StandardFormula,
+            StandardLayers,
+        },
+        Modifier,
+    },
+    raw::{Finite, StandardRaw},
+    send::StatS,
+    stat::{Stat, StatMarker},
+};
+
+pub mod reload;
+
+/// Formula of definitions without a `formula` field, over the default layer names.
+pub const STANDARD_FORMULA: &str =
+    "((base + flat) * additive + post_add) * multiplicative + post_mul";
+
+const LAYER_KINDS: [&str; 5] = ["flat", "additive", "post_add", "multiplicative", "post_mul"];
+
+/// Format of a definition file, picked from its extension by [`Definitions::load`].
+///
+/// Each format needs its crate feature: `ron`, `toml` or `json`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Format {
+    Ron,
+    Toml,
+    Json,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ron" => Some(Format::Ron),
+            "toml" => Some(Format::Toml),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    fn parse(self, source: &str) -> Result<DefinitionFile, DefinitionErrorKind> {
+        match self {
+            #[cfg(feature = "ron")]
+            Format::Ron => {
+                ron::from_str(source).map_err(|e| DefinitionErrorKind::Parse(e.to_string()))
+            }
+            #[cfg(feature = "toml")]
+            Format::Toml => {
+                toml::from_str(source).map_err(|e| DefinitionErrorKind::Parse(e.to_string()))
+            }
+            #[cfg(feature = "json")]
+            Format::Json => {
+                serde_json::from_str(source).map_err(|e| DefinitionErrorKind::Parse(e.to_string()))
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = source;
+                Err(DefinitionErrorKind::UnsupportedFormat)
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct DefinitionFile {
+    stats: Vec<StatDefinition>,
+}
+
+/// Stat type described by a definition file.
+///
+/// `layers` maps the formula's variable names to the standard layers (`flat`, `additive`,
+/// `post_add`, `multiplicative` and `post_mul`). Without `layers` and `formula` the stat
+/// uses [`STANDARD_FORMULA`].
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatDefinition {
+    pub name: String,
+    pub raw: String,
+    pub base: f64,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    #[serde(default)]
+    pub layers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub formula: Option<String>,
+}
+
+impl StatDefinition {
+    fn validate(&self) -> Result<(), (&'static str, DefinitionErrorKind)> {
+        match self.raw.as_str() {
+            "f32" => self.validate_as::<f32>(),
+            "f64" => self.validate_as::<f64>(),
+            _ => Err(("raw", DefinitionErrorKind::UnknownRaw(self.raw.clone()))),
+        }
+    }
+
+    /// Checks the values once converted to `Raw`, as they may not fit in it.
+    fn validate_as<Raw>(&self) -> Result<(), (&'static str, DefinitionErrorKind)>
+    where
+        Raw: DefinedRaw,
+    {
+        for (field, value) in [
+            ("base", Some(self.base)),
+            ("min", self.min),
+            ("max", self.max),
+        ] {
+            if value.is_some_and(|v| !Raw::from_f64(v).is_finite()) {
+                return Err((field, DefinitionErrorKind::NonFinite));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Err(("max", DefinitionErrorKind::InvalidBounds));
+            }
+        }
+        self.formula::<Raw, ()>().map(|_| ())
+    }
+
+    fn formula<Raw, Metadata>(
+        &self,
+    ) -> Result<
+        DynStatFormula<Raw, DefinedLayers<Raw, Metadata>>,
+        (&'static str, DefinitionErrorKind),
+    >
+    where
+        Raw: DefinedRaw,
+        Metadata: PartialEq + Clone + Copy + 'static,
+    {
+        let mut bindings = Bindings::<Defined<Raw>, DefinedLayers<Raw, Metadata>>::new();
+        let source = match &self.formula {
+            Some(formula) => {
+                for (name, kind) in &self.layers {
+                    bind_layer(&mut bindings, name, kind).ok_or_else(|| {
+                        ("layers", DefinitionErrorKind::UnknownLayer(kind.clone()))
+                    })?;
+                }
+                formula.as_str()
+            }
+            None if self.layers.is_empty() => {
+                for kind in LAYER_KINDS {
+                    bind_layer(&mut bindings, kind, kind);
+                }
+                STANDARD_FORMULA
+            }
+            None => return Err(("formula", DefinitionErrorKind::MissingFormula)),
+        };
+        let expression = Expression::parse(source)
+            .and_then(|e| e.compile(&bindings))
+            .map_err(|e| ("formula", DefinitionErrorKind::Expression(e)))?;
+        // Custom formulas are named after their expression, which breakdowns show.
+        let expression = Arc::new(expression);
+        Ok(match self.formula {
+            Some(_) => Formula::named(source, expression),
+            None => Formula::anonymous(expression),
+        })
+    }
+}
+
+fn bind_layer<Raw, Metadata>(
+    bindings: &mut Bindings<Defined<Raw>, DefinedLayers<Raw, Metadata>>,
+    name: &str,
+    kind: &str,
+) -> Option<()>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    match kind {
+        "flat" => bindings.bind_layer::<Flat<Defined<Raw>, Raw, Metadata>, _>(name),
+        "additive" => bindings.bind_layer::<Additive<Defined<Raw>, Raw, Metadata>, _>(name),
+        "post_add" => bindings.bind_layer::<PostAdditive<Defined<Raw>, Raw, Metadata>, _>(name),
+        "multiplicative" => {
+            bindings.bind_layer::<Multiplicative<Defined<Raw>, Raw, Metadata>, _>(name)
+        }
+        "post_mul" => {
+            bindings.bind_layer::<PostMultiplicative<Defined<Raw>, Raw, Metadata>, _>(name)
+        }
+        _ => return None,
+    };
+    Some(())
+}
+
+/// Error found while loading definitions or instantiating a stat, with the file, stat and
+/// field it was found in when known.
+#[derive(Debug)]
+pub struct DefinitionError {
+    pub file: Option<PathBuf>,
+    pub stat: Option<String>,
+    pub field: Option<&'static str>,
+    pub kind: DefinitionErrorKind,
+}
+
+#[derive(Debug)]
+pub enum DefinitionErrorKind {
+    Io(io::Error),
+    /// The file is not valid for its format.
+    Parse(String),
+    /// The extension is unknown or its format feature is disabled.
+    UnsupportedFormat,
+    UnknownRaw(String),
+    NonFinite,
+    /// `min` is greater than `max`.
+    InvalidBounds,
+    UnknownLayer(String),
+    /// `layers` is set without a `formula` using them.
+    MissingFormula,
+    Expression(Error),
+    /// Another definition already uses this name.
+    DuplicateStat,
+    NotFound,
+    /// The stat was instantiated with another raw type than the one defined.
+    RawMismatch {
+        expected: String,
+    },
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}: ", file.display())?;
+        }
+        match (&self.stat, self.field) {
+            (Some(stat), Some(field)) => write!(f, "stat `{stat}`, field `{field}`: ")?,
+            (Some(stat), None) => write!(f, "stat `{stat}`: ")?,
+            (None, Some(field)) => write!(f, "field `{field}`: ")?,
+            (None, None) => {}
+        }
+        match &self.kind {
+            DefinitionErrorKind::Io(e) => write!(f, "{e}"),
+            DefinitionErrorKind::Parse(e) => write!(f, "{e}"),
+            DefinitionErrorKind::UnsupportedFormat => write!(f, "unsupported file format"),
+            DefinitionErrorKind::UnknownRaw(raw) => write!(f, "unknown raw type `{raw}`"),
+            DefinitionErrorKind::NonFinite => write!(f, "value is not finite"),
+            DefinitionErrorKind::InvalidBounds => write!(f, "minimum is greater than maximum"),
+            DefinitionErrorKind::UnknownLayer(kind) => write!(f, "unknown layer `{kind}`"),
+            DefinitionErrorKind::MissingFormula => {
+                write!(f, "a formula is required when layers are named")
+            }
+            DefinitionErrorKind::Expression(e) => write!(f, "{e}"),
+            DefinitionErrorKind::DuplicateStat => write!(f, "stat is defined more than once"),
+            DefinitionErrorKind::NotFound => write!(f, "no such stat"),
+            DefinitionErrorKind::RawMismatch { expected } => {
+                write!(f, "stat is defined with raw type `{expected}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DefinitionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            DefinitionErrorKind::Io(e) => Some(e),
+            DefinitionErrorKind::Expression(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Raw types a definition's `raw` field can name.
+pub trait DefinedRaw:
+    StandardRaw<Scalar = Self>
+    + Sub<Output = Self>
+    + Div<Output = Self>
+    + PartialOrd
+    + Finite
+    + FromStr
+    + Send
+    + Sync
+    + 'static
+{
+    const NAME: &'static str;
+
+    fn from_f64(value: f64) -> Self;
+}
+
+impl DefinedRaw for f32 {
+    const NAME: &'static str = "f32";
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl DefinedRaw for f64 {
+    const NAME: &'static str = "f64";
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+/// Marker of the stats instantiated from definitions.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Defined<Raw>(PhantomData<Raw>);
+
+impl<Raw> StatMarker for Defined<Raw>
+where
+    Raw: DefinedRaw,
+{
+    type Raw = Raw;
+}
+
+pub type DefinedLayers<Raw, Metadata> = StandardLayers<Defined<Raw>, Metadata>;
+
+#[derive(Clone, Debug)]
+struct Entry {
+    file: Option<PathBuf>,
+    definition: StatDefinition,
+}
+
+/// Stat definitions loaded from files, by stat name.
+#[derive(Clone, Debug, Default)]
+pub struct Definitions {
+    stats: BTreeMap<String, Entry>,
+}
+
+impl Definitions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads and validates every stat of the file at `path`, replacing the stats previously
+    /// loaded from it. Nothing is loaded if any stat is invalid.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> Result<(), DefinitionError> {
+        let path = path.as_ref();
+        let error = |kind| DefinitionError {
+            file: Some(path.to_path_buf()),
+            stat: None,
+            field: None,
+            kind,
+        };
+        let format =
+            Format::from_path(path).ok_or_else(|| error(DefinitionErrorKind::UnsupportedFormat))?;
+        let source = fs::read_to_string(path).map_err(|e| error(DefinitionErrorKind::Io(e)))?;
+        self.insert(Some(path), &source, format)
+    }
+
+    /// Loads definitions that do not come from a file.
+    pub fn load_str(&mut self, source: &str, format: Format) -> Result<(), DefinitionError> {
+        self.insert(None, source, format)
+    }
+
+    fn insert(
+        &mut self,
+        file: Option<&Path>,
+        source: &str,
+        format: Format,
+    ) -> Result<(), DefinitionError> {
+        let error = |stat: Option<&str>, field, kind| DefinitionError {
+            file: file.map(Path::to_path_buf),
+            stat: stat.map(str::to_string),
+            field,
+            kind,
+        };
+        let parsed = format
+            .parse(source)
+            .map_err(|kind| error(None, None, kind))?;
+
+        let mut loaded = BTreeMap::new();
+        for definition in parsed.stats {
+            definition
+                .validate()
+                .map_err(|(field, kind)| error(Some(&definition.name), Some(field), kind))?;
+            let taken = self
+                .stats
+                .get(&definition.name)
+                .is_some_and(|e| file.is_none() || e.file.as_deref() != file);
+            if taken || loaded.contains_key(&definition.name) {
+                return Err(error(
+                    Some(&definition.name),
+                    Some("name"),
+                    DefinitionErrorKind::DuplicateStat,
+                ));
+            }
+            let entry = Entry {
+                file: file.map(Path::to_path_buf),
+                definition,
+            };
+            loaded.insert(entry.definition.name.clone(), entry);
+        }
+
+        if file.is_some() {
+            self.stats.retain(|_, e| e.file.as_deref() != file);
+        }
+        self.stats.extend(loaded);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StatDefinition> {
+        self.stats.get(name).map(|e| &e.definition)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.stats.keys().map(String::as_str)
+    }
+
+    /// Creates a stat from the definition named `name`.
+    pub fn instantiate<Raw, Metadata>(
+        &self,
+        name: &str,
+    ) -> Result<DefinedStat<Raw, Metadata>, DefinitionError>
+    where
+        Raw: DefinedRaw,
+        Metadata: PartialEq + Clone + Copy + Send + Sync + 'static,
+    {
+        Ok(DefinedStat {
+            name: intern(name),
+            cell: StatCell(Mutex::new(self.build(name)?)),
+        })
+    }
+
+    fn build<Raw, Metadata>(
+        &self,
+        name: &str,
+    ) -> Result<DefinedInner<Raw, Metadata>, DefinitionError>
+    where
+        Raw: DefinedRaw,
+        Metadata: PartialEq + Clone + Copy + Send + Sync + 'static,
+    {
+        let entry = self.stats.get(name).ok_or_else(|| DefinitionError {
+            file: None,
+            stat: Some(name.to_string()),
+            field: None,
+            kind: DefinitionErrorKind::NotFound,
+        })?;
+        let error = |field, kind| DefinitionError {
+            file: entry.file.clone(),
+            stat: Some(name.to_string()),
+            field: Some(field),
+            kind,
+        };
+        let definition = &entry.definition;
+        if definition.raw != Raw::NAME {
+            return Err(error(
+                "raw",
+                DefinitionErrorKind::RawMismatch {
+                    expected: definition.raw.clone(),
+                },
+            ));
+        }
+        let formula = definition
+            .formula()
+            .map_err(|(field, kind)| error(field, kind))?;
+        let formula = match (definition.min, definition.max) {
+            (None, None) => formula,
+            (min, max) => {
+                let name = formula.name().map(Arc::<str>::from);
+                let bounded = Arc::new(Bounded {
+                    formula,
+                    min: min.map(Raw::from_f64),
+                    max: max.map(Raw::from_f64),
+                }) as Arc<_>;
+                match name {
+                    Some(name) => Formula::named(name, bounded),
+                    None => Formula::anonymous(bounded),
+                }
+            }
+        };
+
+        Ok(Stat::from_formula(Raw::from_f64(definition.base), formula))
+    }
+}
+
+/// Names and formulas of the instantiated stats, leaked once each so [`DynStat::name`] and
+/// [`Breakdown::formula`] can return them.
+fn intern(name: &str) -> &'static str {
+    static NAMES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+    let mut names = NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+    match names.get(name) {
+        Some(name) => name,
+        None => {
+            let name = Box::leak(name.into());
+            names.insert(name);
+            name
+        }
+    }
+}
+
+type DefinedFormula<Raw, Metadata> = DynStatFormula<Raw, DefinedLayers<Raw, Metadata>>;
+
+type DefinedInner<Raw, Metadata> =
+    Stat<Defined<Raw>, DefinedLayers<Raw, Metadata>, DefinedFormula<Raw, Metadata>>;
+
+/// Formula of a definition with `min` or `max`, whose bounds differ between stats of the
+/// same [`Defined`] marker and so cannot go through [`StatMarker::clamp`].
+struct Bounded<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    formula: DefinedFormula<Raw, Metadata>,
+    min: Option<Raw>,
+    max: Option<Raw>,
+}
+
+impl<Raw, Metadata> StatFormula<Raw, DefinedLayers<Raw, Metadata>> for Bounded<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    fn eval(&self, base: Raw, layers: &DefinedLayers<Raw, Metadata>) -> Raw {
+        let value = self.formula.eval(base, layers);
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => min,
+            (_, Some(max)) if value > max => max,
+            _ => value,
+        }
+    }
+}
+
+/// Stat instantiated from a [`StatDefinition`], with the standard layers behind a `Mutex`.
+pub struct DefinedStat<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    name: &'static str,
+    cell: StatS<Defined<Raw>, DefinedLayers<Raw, Metadata>, DefinedFormula<Raw, Metadata>>,
+}
+
+macro_rules! impl_defined_layers {
+    ($($layer:ident: $m:ident),+) => {
+        $crate::__paste! {
+            impl<Raw, Metadata> DefinedStat<Raw, Metadata>
+            where
+                Raw: DefinedRaw,
+                Metadata: PartialEq + Clone + Copy,
+            {
+                $(
+                    pub fn [<apply_ $layer>](&self, value: $m<Defined<Raw>, Raw, Metadata>) {
+                        self.cell.apply(value);
+                    }
+
+                    pub fn [<try_apply_ $layer>](
+                        &self,
+                        value: $m<Defined<Raw>, Raw, Metadata>,
+                    ) -> Result<(), Error> {
+                        self.cell.try_apply(value)
+                    }
+
+                    pub fn [<remove_ $layer>](&self, value: $m<Defined<Raw>, Raw, Metadata>) {
+                        self.cell.remove(value);
+                    }
+
+                    pub fn [<try_remove_ $layer>](
+                        &self,
+                        value: $m<Defined<Raw>, Raw, Metadata>,
+                    ) -> Result<(), Error> {
+                        self.cell.try_remove(value)
+                    }
+
+                    pub fn [<has_ $layer>](&self, value: $m<Defined<Raw>, Raw, Metadata>) -> bool {
+                        self.cell.has(value)
+                    }
+
+                    pub fn [<try_has_ $layer>](
+                        &self,
+                        value: $m<Defined<Raw>, Raw, Metadata>,
+                    ) -> Result<bool, Error> {
+                        self.cell.try_has(value)
+                    }
+
+                    pub fn [<for_each_ $layer>](
+                        &self,
+                        f: impl FnMut(&$m<Defined<Raw>, Raw, Metadata>),
+                    ) {
+                        self.cell.for_each(f);
+                    }
+
+                    pub fn [<try_for_each_ $layer>](
+                        &self,
+                        f: impl FnMut(&$m<Defined<Raw>, Raw, Metadata>),
+                    ) -> Result<(), Error> {
+                        self.cell.try_for_each(f)
+                    }
+                )+
+            }
+        }
+    };
+}
+
+impl_defined_layers!(
+    flat: Flat,
+    additive: Additive,
+    post_add: PostAdditive,
+    multiplicative: Multiplicative,
+    post_mul: PostMultiplicative
+);
+
+impl<Raw, Metadata> DefinedStat<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn base(&self) -> Raw {
+        self.cell.base()
+    }
+
+    pub fn try_base(&self) -> Result<Raw, Error> {
+        self.cell.try_base()
+    }
+
+    /// Value of the formula, kept within the defined bounds.
+    pub fn get(&self) -> Raw {
+        self.cell.get()
+    }
+
+    pub fn try_get(&self) -> Result<Raw, Error> {
+        self.cell.try_get()
+    }
+
+    pub fn clear(&self) {
+        self.cell.clear();
+    }
+
+    pub fn try_clear(&self) -> Result<(), Error> {
+        self.cell.try_clear()
+    }
+
+    /// Removes every modifier carrying `source` as metadata.
+    pub fn clear_source(&self, source: Metadata) {
+        self.cell.edit(|stat| Self::clear_source_of(stat, source));
+    }
+
+    pub fn try_clear_source(&self, source: Metadata) -> Result<(), Error> {
+        self.cell
+            .try_edit(|stat| Self::clear_source_of(stat, source))
+    }
+
+    fn clear_source_of(stat: &mut DefinedInner<Raw, Metadata>, source: Metadata) {
+        stat.retain(|m: &mut Flat<Defined<Raw>, Raw, Metadata>| m.metadata() != Some(source));
+        stat.retain(|m: &mut Additive<Defined<Raw>, Raw, Metadata>| m.metadata() != Some(source));
+        stat.retain(|m: &mut PostAdditive<Defined<Raw>, Raw, Metadata>| {
+            m.metadata() != Some(source)
+        });
+        stat.retain(|m: &mut Multiplicative<Defined<Raw>, Raw, Metadata>| {
+            m.metadata() != Some(source)
+        });
+        stat.retain(|m: &mut PostMultiplicative<Defined<Raw>, Raw, Metadata>| {
+            m.metadata() != Some(source)
+        });
+    }
+
+    pub fn breakdown(&self) -> Breakdown<Raw> {
+        self.breakdown_of(&WithMutex::read(&self.cell.0))
+    }
+
+    pub fn try_breakdown(&self) -> Result<Breakdown<Raw>, Error> {
+        Ok(self.breakdown_of(&*WithMutex::try_read(&self.cell.0)?))
+    }
+
+    fn breakdown_of(&self, stat: &DefinedInner<Raw, Metadata>) -> Breakdown<Raw> {
+        Breakdown {
+            name: self.name,
+            formula: stat.formula_name().map(intern),
+            base: stat.base(),
+            flat: Flat::combine(stat.m1()),
+            additive: Additive::combine(stat.m2()),
+            post_add: PostAdditive::combine(stat.m3()),
+            multiplicative: Multiplicative::combine(stat.m4()),
+            post_mul: PostMultiplicative::combine(stat.m5()),
+            value: stat.get(),
+        }
+    }
+
+    /// Takes the base, formula and bounds of `new`, keeping the applied modifiers.
+    fn redefine(&self, new: DefinedInner<Raw, Metadata>) {
+        self.cell.edit(|stat| {
+            stat.set_base(new.base());
+            stat.set_formula(new.formula().clone());
+        });
+    }
+}
+
+impl<Raw, Metadata> StatAccess for DefinedStat<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    type Marker = Defined<Raw>;
+
+    type Layers = DefinedLayers<Raw, Metadata>;
+
+    fn apply<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>,
+    {
+        self.cell.get_mut().apply(value);
+    }
+
+    fn remove<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>,
+    {
+        self.cell.get_mut().remove(value);
+    }
+
+    fn has<M, I>(&self, value: M) -> bool
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>,
+    {
+        self.cell.has(value)
+    }
+
+    fn for_each<M, I>(&self, f: impl FnMut(&M))
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>,
+    {
+        self.cell.for_each(f);
+    }
+
+    fn base(&self) -> Raw {
+        self.cell.base()
+    }
+
+    fn get(&self) -> Raw {
+        self.cell.get()
+    }
+}
+
+impl<Raw, Metadata> StatAccess for &DefinedStat<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    type Marker = Defined<Raw>;
+
+    type Layers = DefinedLayers<Raw, Metadata>;
+
+    fn apply<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>,
+    {
+        self.cell.apply(value);
+    }
+
+    fn remove<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>,
+    {
+        self.cell.remove(value);
+    }
+
+    fn has<M, I>(&self, value: M) -> bool
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>,
+    {
+        self.cell.has(value)
+    }
+
+    fn for_each<M, I>(&self, f: impl FnMut(&M))
+    where
+        M: Modifier,
+        Self::Layers: Selector<M, I>,
+    {
+        self.cell.for_each(f);
+    }
+
+    fn base(&self) -> Raw {
+        self.cell.base()
+    }
+
+    fn get(&self) -> Raw {
+        self.cell.get()
+    }
+}
+
+impl<Raw, Metadata> DynStat<Raw, Metadata> for DefinedStat<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy + Send + Sync + 'static,
+{
+    /// Creates an unnamed stat with the [`STANDARD_FORMULA`] and no bounds.
+    fn from_base(base: Option<Raw>) -> Self
+    where
+        Raw: Default,
+    {
+        let formula = Formula::anonymous(Arc::new(StandardFormula) as Arc<_>);
+        Self {
+            name: Defined::<Raw>::name(),
+            cell: StatCell::from_formula(base.unwrap_or_default(), formula),
+        }
+    }
+
+    fn marker_name() -> &'static str {
+        Defined::<Raw>::name()
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn base(&self) -> Raw {
+        DefinedStat::base(self)
+    }
+
+    fn get(&self) -> Raw {
+        DefinedStat::get(self)
+    }
+
+    fn clear(&self) {
+        DefinedStat::clear(self)
+    }
+
+    fn clear_source(&self, source: Metadata) {
+        DefinedStat::clear_source(self, source)
+    }
+
+    fn breakdown(&self) -> Breakdown<Raw> {
+        DefinedStat::breakdown(self)
+    }
+}
+
+impl<Raw, Metadata> fmt::Debug for DefinedStat<Raw, Metadata>
+where
+    Raw: DefinedRaw + fmt::Debug,
+    Metadata: PartialEq + Clone + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("DefinedStat");
+        debug.field("name", &self.name);
+        match WithMutex::try_read(&self.cell.0) {
+            Ok(stat) => debug
+                .field("base", &stat.base())
+                .field("value", &stat.get()),
+            Err(e) => debug.field("stat", &format_args!("<{e}>")),
+        }
+        .finish()
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    /// Path in the temporary directory unique to this test run, as several test binaries
+    /// may run at once.
+    pub(crate) fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("maxstat_{}_{name}", std::process::id()))
+    }
+
+    const JSON: &str = r#"{
+        "stats": [
+            { "name": "Health", "raw": "f32", "base": 100, "max": 250 },
+            {
+                "name": "Damage",
+                "raw": "f64",
+                "base": 10,
+                "layers": { "flat": "flat", "inc": "additive", "more": "multiplicative" },
+                "formula": "(base + flat) * inc * more + 1"
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_instantiate() {
+        let mut definitions = Definitions::new();
+        definitions.load_str(JSON, Format::Json).unwrap();
+
+        assert_eq!(
+            vec!["Damage", "Health"],
+            definitions.names().collect::<Vec<_>>()
+        );
+
+        let health = definitions.instantiate::<f32, ()>("Health").unwrap();
+        health.apply_flat(Flat::from_raw(20.));
+        health.apply_multiplicative(Multiplicative::from_raw(2.));
+
+        assert_eq!(240., health.get());
+
+        health.apply_post_mul(PostMultiplicative::from_raw(20.));
+
+        assert_eq!(250., health.get());
+        assert_eq!(
+            r#"DefinedStat { name: "Health", base: 100.0, value: 250.0 }"#,
+            format!("{health:?}")
+        );
+
+        let damage = definitions.instantiate::<f64, ()>("Damage").unwrap();
+        damage.apply_flat(Flat::from_raw(5.));
+        damage.apply_additive(Additive::from_raw(1.));
+        damage.apply_post_add(PostAdditive::from_raw(100.));
+
+        assert_eq!(31., damage.get());
+        assert_eq!("Damage", damage.name());
+        assert_eq!(
+            "Damage: (base + flat) * inc * more + 1 = 31",
+            damage.breakdown().to_string()
+        );
+
+        let mut count = 0;
+        damage.for_each_flat(|_| count += 1);
+        assert_eq!(1, count);
+        assert_eq!(
+            Err(Error::InvalidValue { layer: 1 }),
+            damage.try_apply_flat(Flat::from_raw(f64::NAN))
+        );
+        assert_eq!(
+            Err(Error::NotFound { layer: 1 }),
+            damage.try_remove_flat(Flat::from_raw(1.))
+        );
+
+        let error = definitions.instantiate::<f32, ()>("Damage").unwrap_err();
+        assert_eq!(
+            "stat `Damage`, field `raw`: stat is defined with raw type `f64`",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let path = temp_path("test_errors.json");
+        fs::write(
+            &path,
+            r#"{ "stats": [{ "name": "Armor", "raw": "f32", "base": 1, "layers": { "a": "flat" }, "formula": "base + b" }] }"#,
+        )
+        .unwrap();
+
+        let mut definitions = Definitions::new();
+        let error = definitions.load(&path).unwrap_err();
+
+        assert_eq!(Some(path.clone()), error.file);
+        assert_eq!(Some("Armor"), error.stat.as_deref());
+        assert_eq!(Some("formula"), error.field);
+        assert!(matches!(
+            error.kind,
+            DefinitionErrorKind::Expression(Error::UnknownVariable { position: 7 })
+        ));
+
+        fs::remove_file(&path).unwrap();
+
+        let invalid = [
+            (r#"{ "name": "A", "raw": "u8", "base": 1 }"#, "raw"),
+            (r#"{ "name": "A", "raw": "f32", "base": 1e39 }"#, "base"),
+            (
+                r#"{ "name": "A", "raw": "f32", "base": 1, "max": 1e39 }"#,
+                "max",
+            ),
+            (
+                r#"{ "name": "A", "raw": "f32", "base": 1, "min": 2, "max": 1 }"#,
+                "max",
+            ),
+            (
+                r#"{ "name": "A", "raw": "f32", "base": 1, "layers": { "a": "flat" } }"#,
+                "formula",
+            ),
+            (
+                r#"{ "name": "A", "raw": "f32", "base": 1, "layers": { "a": "bonus" }, "formula": "a" }"#,
+                "layers",
+            ),
+        ];
+        for (stat, field) in invalid {
+            let error = definitions
+                .load_str(&format!(r#"{{ "stats": [{stat}] }}"#), Format::Json)
+                .unwrap_err();
+
+            assert_eq!(Some(field), error.field);
+        }
+
+        let error = definitions
+            .load_str(r#"{ "stats": [{ "name": "A" }] }"#, Format::Json)
+            .unwrap_err();
+        assert!(matches!(error.kind, DefinitionErrorKind::Parse(_)));
+
+        definitions.load_str(JSON, Format::Json).unwrap();
+        let error = definitions.load_str(JSON, Format::Json).unwrap_err();
+        assert_eq!(Some("name"), error.field);
+    }
+
+    fn sourced(raw: f32) -> Flat<Defined<f32>, f32, u8> {
+        Flat::from_raw(raw).set_metadata(Some(1)).build()
+    }
+
+    /// Raises any stat by a flat amount.
+    fn buff<S, I>(mut stat: S) -> S
+    where
+        S: StatAccess<Marker = Defined<f32>>,
+        S::Layers: Selector<Flat<Defined<f32>, f32, u8>, I>,
+    {
+        stat.apply(sourced(1.));
+        stat
+    }
+
+    #[test]
+    fn test_dyn_stat() {
+        let mut definitions = Definitions::new();
+        definitions.load_str(JSON, Format::Json).unwrap();
+
+        let health = definitions.instantiate::<f32, u8>("Health").unwrap();
+        buff(&health);
+        let health = buff(health);
+
+        assert_eq!(102., StatAccess::get(&health));
+        assert!(StatAccess::has(&health, sourced(1.)));
+
+        let stat: &dyn DynStat<f32, u8> = &health;
+        health.apply_post_mul(PostMultiplicative::from_raw(500.));
+
+        assert_eq!("Health", stat.name());
+        assert_eq!(250., stat.breakdown().value);
+        assert_eq!(
+            "Health: ((base 100 + flat 2) * additive 1 + post_add 0) * multiplicative 1 + \
+             post_mul 500 = 250",
+            stat.breakdown().to_string()
+        );
+
+        stat.clear_source(1);
+
+        assert_eq!(250., stat.get());
+        assert_eq!(0., stat.breakdown().flat);
+
+        let unnamed = DefinedStat::<f32, u8>::from_base(Some(3.));
+
+        assert_eq!(("Defined", 3.), (unnamed.name(), unnamed.get()));
+    }
+
+    #[cfg(all(feature = "ron", feature = "toml"))]
+    #[test]
+    fn test_formats() {
+        let ron = r#"(stats: [(name: "Health", raw: "f32", base: 100.0, max: Some(250.0))])"#;
+        let toml = "[[stats]]\nname = \"Health\"\nraw = \"f32\"\nbase = 100.0\nmax = 250.0\n";
+
+        let mut definitions = Definitions::new();
+        definitions.load_str(JSON, Format::Json).unwrap();
+        let json = definitions.get("Health").cloned();
+        for (source, format) in [(ron, Format::Ron), (toml, Format::Toml)] {
+            let mut definitions = Definitions::new();
+            definitions.load_str(source, format).unwrap();
+
+            assert_eq!(json.as_ref(), definitions.get("Health"));
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Weak},
+    time::SystemTime,
+};
+
+use super::{DefinedRaw, DefinedStat, DefinitionError, DefinitionErrorKind, Definitions};
+
+/// Stat whose value changed when its definition was reloaded.
+#[derive(Debug)]
+pub struct Change<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    pub stat: Arc<DefinedStat<Raw, Metadata>>,
+    pub old: Raw,
+    pub new: Raw,
+}
+
+/// Definitions reloaded from their files when these change, updating the base, formula and
+/// bounds of every live stat instantiated through it while keeping its modifiers.
+///
+/// Files are watched by polling their modification time: call [`poll`](Self::poll) once
+/// per frame or from a background thread.
+///
+/// A stat's raw type is fixed when it is instantiated: live stats whose definition changes
+/// its `raw` keep their last definition, and take the new one only once instantiated again
+/// through a [`Reloader`] of the new raw type, usually after a restart.
+pub struct Reloader<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    definitions: Definitions,
+    files: BTreeMap<PathBuf, Option<SystemTime>>,
+    stats: Vec<Weak<DefinedStat<Raw, Metadata>>>,
+}
+
+impl<Raw, Metadata> Reloader<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            definitions: Definitions::new(),
+            files: BTreeMap::new(),
+            stats: Vec::new(),
+        }
+    }
+
+    /// Loads the file at `path` and watches it from now on.
+    pub fn watch(&mut self, path: impl AsRef<Path>) -> Result<(), DefinitionError> {
+        let path = path.as_ref();
+        let modified = modified(path);
+        self.definitions.load(path)?;
+        self.files.insert(path.to_path_buf(), modified);
+        Ok(())
+    }
+
+    pub fn definitions(&self) -> &Definitions {
+        &self.definitions
+    }
+
+    /// Creates a stat from the definition named `name`, updated on every reload.
+    pub fn instantiate(
+        &mut self,
+        name: &str,
+    ) -> Result<Arc<DefinedStat<Raw, Metadata>>, DefinitionError> {
+        let stat = Arc::new(self.definitions.instantiate(name)?);
+        self.stats.push(Arc::downgrade(&stat));
+        Ok(stat)
+    }
+
+    /// Reloads the files modified since they were last loaded.
+    ///
+    /// Nothing is updated if any definition is invalid, and every modified file is loaded
+    /// again on the next poll. Deleted files are skipped until they reappear, keeping the
+    /// definitions last loaded from them.
+    pub fn poll(&mut self) -> Result<Vec<Change<Raw, Metadata>>, DefinitionError> {
+        let modified = self
+            .files
+            .iter()
+            .filter_map(|(path, &time)| {
+                let new = modified(path)?;
+                (time != Some(new)).then(|| (path.clone(), Some(new)))
+            })
+            .collect();
+        self.reload_files(modified)
+    }
+
+    /// Reloads every watched file that still exists.
+    pub fn reload(&mut self) -> Result<Vec<Change<Raw, Metadata>>, DefinitionError> {
+        let files = self
+            .files
+            .keys()
+            .filter_map(|path| Some((path.clone(), Some(modified(path)?))))
+            .collect();
+        self.reload_files(files)
+    }
+
+    fn reload_files(
+        &mut self,
+        files: Vec<(PathBuf, Option<SystemTime>)>,
+    ) -> Result<Vec<Change<Raw, Metadata>>, DefinitionError> {
+        if files.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut definitions = self.definitions.clone();
+        for (path, _) in &files {
+            definitions.load(path)?;
+        }
+
+        self.stats.retain(|stat| stat.strong_count() > 0);
+        let mut updates = Vec::new();
+        for stat in self.stats.iter().filter_map(Weak::upgrade) {
+            match definitions.build(stat.name()) {
+                Ok(inner) => updates.push((stat, inner)),
+                // Stats whose definition was removed or changed its raw type keep the last one.
+                Err(DefinitionError {
+                    kind: DefinitionErrorKind::NotFound | DefinitionErrorKind::RawMismatch { .. },
+                    ..
+                }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.definitions = definitions;
+        self.files.extend(files);
+
+        Ok(updates
+            .into_iter()
+            .filter_map(|(stat, inner)| {
+                let old = stat.get();
+                stat.redefine(inner);
+                let new = stat.get();
+                (old != new).then_some(Change { stat, old, new })
+            })
+            .collect())
+    }
+}
+
+impl<Raw, Metadata> Default for Reloader<Raw, Metadata>
+where
+    Raw: DefinedRaw,
+    Metadata: PartialEq + Clone + Copy + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use std::{fs::File, time::Duration};
+
+    use super::*;
+    use crate::{
+        definition::tests::temp_path,
+        modifier::{standard::Flat, Modifier},
+    };
+
+    fn write(path: &Path, source: &str, age: u64) {
+        fs::write(path, source).unwrap();
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(age))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_reload() {
+        let path = temp_path("test_reload.json");
+        write(
+            &path,
+            r#"{ "stats": [
+                { "name": "Health", "raw": "f32", "base": 100 },
+                { "name": "Mana", "raw": "f32", "base": 50 }
+            ] }"#,
+            1,
+        );
+
+        let mut reloader = Reloader::<f32, ()>::new();
+        reloader.watch(&path).unwrap();
+        let health = reloader.instantiate("Health").unwrap();
+        let mana = reloader.instantiate("Mana").unwrap();
+        health.apply_flat(Flat::from_raw(10.));
+
+        assert!(reloader.poll().unwrap().is_empty());
+
+        write(
+            &path,
+            r#"{ "stats": [
+                { "name": "Health", "raw": "f32", "base": 120, "max": 125 },
+                { "name": "Mana", "raw": "f32", "base": 50 }
+            ] }"#,
+            2,
+        );
+        let changes = reloader.poll().unwrap();
+
+        assert_eq!(1, changes.len());
+        assert!(Arc::ptr_eq(&health, &changes[0].stat));
+        assert_eq!((110., 125.), (changes[0].old, changes[0].new));
+        assert!(health.has_flat(Flat::from_raw(10.)));
+        assert_eq!(50., mana.get());
+
+        write(&path, r#"{ "stats": [{ "name": "Health" }] }"#, 3);
+
+        assert!(reloader.poll().is_err());
+        assert!(reloader.poll().is_err());
+        assert_eq!(120., health.base());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_partial_failure() {
+        let broken = temp_path("test_partial_failure_a.json");
+        let valid = temp_path("test_partial_failure_b.json");
+        write(
+            &broken,
+            r#"{ "stats": [{ "name": "Health", "raw": "f32", "base": 100 }] }"#,
+            1,
+        );
+        write(
+            &valid,
+            r#"{ "stats": [{ "name": "Mana", "raw": "f32", "base": 50 }] }"#,
+            1,
+        );
+
+        let mut reloader = Reloader::<f32, ()>::new();
+        reloader.watch(&broken).unwrap();
+        reloader.watch(&valid).unwrap();
+        let mana = reloader.instantiate("Mana").unwrap();
+
+        write(&broken, r#"{ "stats": [{ "name": "Health" }] }"#, 2);
+        write(
+            &valid,
+            r#"{ "stats": [{ "name": "Mana", "raw": "f32", "base": 60 }] }"#,
+            2,
+        );
+
+        assert!(reloader.poll().is_err());
+        assert_eq!(50., mana.get());
+
+        write(
+            &broken,
+            r#"{ "stats": [{ "name": "Health", "raw": "f32", "base": 100 }] }"#,
+            3,
+        );
+        let changes = reloader.poll().unwrap();
+
+        assert_eq!(1, changes.len());
+        assert_eq!((50., 60.), (changes[0].old, changes[0].new));
+
+        fs::remove_file(&broken).unwrap();
+        fs::remove_file(&valid).unwrap();
+    }
+
+    #[test]
+    fn test_raw_change() {
+        let path = temp_path("test_raw_change.json");
+        write(
+            &path,
+            r#"{ "stats": [
+                { "name": "Health", "raw": "f32", "base": 100 },
+                { "name": "Mana", "raw": "f32", "base": 50 }
+            ] }"#,
+            1,
+        );
+
+        let mut reloader = Reloader::<f32, ()>::new();
+        reloader.watch(&path).unwrap();
+        let health = reloader.instantiate("Health").unwrap();
+        let mana = reloader.instantiate("Mana").unwrap();
+
+        write(
+            &path,
+            r#"{ "stats": [
+                { "name": "Health", "raw": "f64", "base": 120 },
+                { "name": "Mana", "raw": "f32", "base": 60 }
+            ] }"#,
+            2,
+        );
+        let changes = reloader.poll().unwrap();
+
+        assert_eq!(1, changes.len());
+        assert!(Arc::ptr_eq(&mana, &changes[0].stat));
+        assert_eq!(100., health.get());
+        assert!(reloader.instantiate("Health").is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_deleted_file() {
+        let path = temp_path("test_deleted_file.json");
+        write(
+            &path,
+            r#"{ "stats": [{ "name": "Health", "raw": "f32", "base": 100 }] }"#,
+            1,
+        );
+
+        let mut reloader = Reloader::<f32, ()>::new();
+        reloader.watch(&path).unwrap();
+        let health = reloader.instantiate("Health").unwrap();
+
+        fs::remove_file(&path).unwrap();
+
+        assert!(reloader.poll().unwrap().is_empty());
+        assert!(reloader.poll().unwrap().is_empty());
+        assert!(reloader.reload().unwrap().is_empty());
+        assert_eq!(100., health.get());
+
+        write(
+            &path,
+            r#"{ "stats": [{ "name": "Health", "raw": "f32", "base": 120 }] }"#,
+            2,
+        );
+        let changes = reloader.poll().unwrap();
+
+        assert_eq!(1, changes.len());
+        assert_eq!((100., 120.), (changes[0].old, changes[0].new));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
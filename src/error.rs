@@ -0,0 +1,75 @@
+use std::fmt;
+
+/// Errors reported by the fallible stat operations.
+///
+/// Layers are numbered like the `mN` accessors of the stats, with `0` standing for the
+/// base value.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A modifier with a non-finite raw value was rejected.
+    InvalidValue { layer: usize },
+    /// Evaluation produced a non-finite value. `None` means every layer was finite and
+    /// the formula itself produced it.
+    NonFinite { layer: Option<usize> },
+    /// A pipeline applies the same layer more than once.
+    DuplicateLayer { layer: usize },
+    /// A pipeline never applies one of the stat's layers.
+    MissingLayer { layer: usize },
+    /// A pipeline applies a layer after one that comes later in the stat's layers.
+    LayerOrder { layer: usize },
+    /// A formula expression failed to parse at byte offset `position`.
+    Syntax {
+        position: usize,
+        expected: &'static str,
+    },
+    /// A formula expression uses a variable with no binding at byte offset `position`.
+    UnknownVariable { position: usize },
+    /// An expression nests deeper than [`MAX_DEPTH`](crate::expr::MAX_DEPTH).
+    TooDeep { position: usize },
+    /// A thread panicked while holding the lock of the stat.
+    Poisoned,
+    /// The `RefCell` of the stat is already borrowed, e.g. by a reentrant call.
+    AlreadyBorrowed,
+    /// The modifier to remove is not in its layer.
+    NotFound { layer: usize },
+    /// The layer could not grow to hold one more modifier.
+    Capacity { layer: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidValue { layer } => {
+                write!(f, "rejected non-finite modifier for layer {layer}")
+            }
+            Error::NonFinite { layer: Some(0) } => write!(f, "base value is not finite"),
+            Error::NonFinite { layer: Some(layer) } => {
+                write!(f, "layer {layer} produced a non-finite value")
+            }
+            Error::NonFinite { layer: None } => {
+                write!(f, "formula produced a non-finite value")
+            }
+            Error::DuplicateLayer { layer } => {
+                write!(f, "layer {layer} is applied more than once")
+            }
+            Error::MissingLayer { layer } => write!(f, "layer {layer} is never applied"),
+            Error::LayerOrder { layer } => write!(f, "layer {layer} is applied out of order"),
+            Error::Syntax { position, expected } => {
+                write!(f, "expected {expected} at position {position}")
+            }
+            Error::UnknownVariable { position } => {
+                write!(f, "unknown variable at position {position}")
+            }
+            Error::TooDeep { position } => {
+                write!(f, "expression nests too deeply at position {position}")
+            }
+            Error::Poisoned => write!(f, "stat lock poisoned by a panicking thread"),
+            Error::AlreadyBorrowed => write!(f, "stat is already borrowed"),
+            Error::NotFound { layer } => write!(f, "modifier not found in layer {layer}"),
+            Error::Capacity { layer } => write!(f, "layer {layer} cannot hold more modifiers"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
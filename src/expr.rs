@@ -0,0 +1,535 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{Div, Sub},
+    str::FromStr,
+    sync::Arc,
+};
+
+use crate::{
+    error::Error, formula::StatFormula, layer::Selector, modifier::Modifier, raw::StandardRaw,
+    stat::StatMarker,
+};
+
+/// Parsed formula expression such as `(base + flat) * (1 + inc) * more + post`.
+///
+/// Expressions are made of numbers, variables, `+`, `-`, `*`, `/` and parentheses. `base`
+/// stands for the stat's base value; every other variable is bound to a layer or another stat
+/// when compiling.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Expression {
+    root: Node,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+enum Node {
+    Number { literal: String, position: usize },
+    Variable { name: String, position: usize },
+    Neg(Box<Node>),
+    Add(Box<Node>, Box<Node>),
+    Sub(Box<Node>, Box<Node>),
+    Mul(Box<Node>, Box<Node>),
+    Div(Box<Node>, Box<Node>),
+}
+
+/// Deepest nesting of operators and parentheses accepted by [`Expression::parse`].
+pub const MAX_DEPTH: usize = 256;
+
+impl Expression {
+    /// Parses `source`, reporting syntax errors with their byte offset.
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        let mut parser = Parser {
+            source,
+            position: 0,
+            depth: 0,
+        };
+        let root = parser.sum()?;
+        parser.skip_whitespace();
+        if parser.position < source.len() {
+            return Err(Error::Syntax {
+                position: parser.position,
+                expected: "an operator or end of input",
+            });
+        }
+        Ok(Self { root })
+    }
+
+    /// Names of the variables used, in order of appearance.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.root.variables(&mut names);
+        names
+    }
+
+    pub fn compile<Marker, Layers>(
+        &self,
+        bindings: &Bindings<Marker, Layers>,
+    ) -> Result<CompiledExpression<Marker, Layers>, Error>
+    where
+        Marker: StatMarker,
+        Marker::Raw: StandardRaw + Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+        <Marker::Raw as StandardRaw>::Scalar: FromStr,
+    {
+        Ok(CompiledExpression {
+            root: Arc::new(self.root.compile(bindings)?),
+        })
+    }
+}
+
+impl FromStr for Expression {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Node {
+    fn variables<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Node::Number { .. } => {}
+            Node::Variable { name, .. } => names.push(name),
+            Node::Neg(a) => a.variables(names),
+            Node::Add(a, b) | Node::Sub(a, b) | Node::Mul(a, b) | Node::Div(a, b) => {
+                a.variables(names);
+                b.variables(names);
+            }
+        }
+    }
+
+    fn compile<Marker, Layers>(
+        &self,
+        bindings: &Bindings<Marker, Layers>,
+    ) -> Result<Compiled<Marker, Layers>, Error>
+    where
+        Marker: StatMarker,
+        Marker::Raw: StandardRaw + Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+        <Marker::Raw as StandardRaw>::Scalar: FromStr,
+    {
+        Ok(match self {
+            Node::Number { literal, position } => {
+                let scalar = literal.parse().map_err(|_| Error::Syntax {
+                    position: *position,
+                    expected: "a number of the stat's raw type",
+                })?;
+                Compiled::Constant(Marker::Raw::splat(scalar))
+            }
+            Node::Variable { name, .. } if name == "base" => Compiled::Base,
+            Node::Variable { name, position } => {
+                if let Some(&layer) = bindings.layers.get(name) {
+                    Compiled::Layer(layer)
+                } else if let Some(stat) = bindings.stats.get(name) {
+                    Compiled::Stat(stat.clone())
+                } else {
+                    return Err(Error::UnknownVariable {
+                        position: *position,
+                    });
+                }
+            }
+            Node::Neg(a) => Compiled::Sub(
+                Box::new(Compiled::Constant(Marker::Raw::zero())),
+                Box::new(a.compile(bindings)?),
+            ),
+            Node::Add(a, b) => Compiled::Add(
+                Box::new(a.compile(bindings)?),
+                Box::new(b.compile(bindings)?),
+            ),
+            Node::Sub(a, b) => Compiled::Sub(
+                Box::new(a.compile(bindings)?),
+                Box::new(b.compile(bindings)?),
+            ),
+            Node::Mul(a, b) => Compiled::Mul(
+                Box::new(a.compile(bindings)?),
+                Box::new(b.compile(bindings)?),
+            ),
+            Node::Div(a, b) => Compiled::Div(
+                Box::new(a.compile(bindings)?),
+                Box::new(b.compile(bindings)?),
+            ),
+        })
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.source[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.source[self.position..].chars().next()
+    }
+
+    /// Goes one level deeper into the tree, which is evaluated recursively.
+    fn nest(&mut self) -> Result<(), Error> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(Error::TooDeep {
+                position: self.position,
+            });
+        }
+        Ok(())
+    }
+
+    fn sum(&mut self) -> Result<Node, Error> {
+        let depth = self.depth;
+        let mut node = self.product()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.nest()?;
+            self.position += 1;
+            let rhs = Box::new(self.product()?);
+            node = match op {
+                '+' => Node::Add(Box::new(node), rhs),
+                _ => Node::Sub(Box::new(node), rhs),
+            };
+        }
+        self.depth = depth;
+        Ok(node)
+    }
+
+    fn product(&mut self) -> Result<Node, Error> {
+        let depth = self.depth;
+        let mut node = self.operand()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.nest()?;
+            self.position += 1;
+            let rhs = Box::new(self.operand()?);
+            node = match op {
+                '*' => Node::Mul(Box::new(node), rhs),
+                _ => Node::Div(Box::new(node), rhs),
+            };
+        }
+        self.depth = depth;
+        Ok(node)
+    }
+
+    fn operand(&mut self) -> Result<Node, Error> {
+        let depth = self.depth;
+        let node = match self.peek() {
+            Some('(') => {
+                self.nest()?;
+                self.position += 1;
+                let node = self.sum()?;
+                if self.peek() != Some(')') {
+                    return Err(Error::Syntax {
+                        position: self.position,
+                        expected: "`)`",
+                    });
+                }
+                self.position += 1;
+                node
+            }
+            Some('-') => {
+                self.nest()?;
+                self.position += 1;
+                Node::Neg(Box::new(self.operand()?))
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let position = self.position;
+                let len = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                let literal = &self.source[position..position + len];
+                if literal.parse::<f64>().is_err() {
+                    return Err(Error::Syntax {
+                        position,
+                        expected: "a number",
+                    });
+                }
+                Node::Number {
+                    literal: literal.to_string(),
+                    position,
+                }
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let position = self.position;
+                let len = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                Node::Variable {
+                    name: self.source[position..position + len].to_string(),
+                    position,
+                }
+            }
+            _ => {
+                return Err(Error::Syntax {
+                    position: self.position,
+                    expected: "a number, variable or `(`",
+                })
+            }
+        };
+        self.depth = depth;
+        Ok(node)
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> usize {
+        let rest = &self.source[self.position..];
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.position += len;
+        len
+    }
+}
+
+/// Values the variables of an [`Expression`] stand for: combined layers of the stat or
+/// values read from other stats.
+pub struct Bindings<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    layers: HashMap<String, fn(&Layers) -> Marker::Raw>,
+    stats: HashMap<String, Arc<dyn Fn() -> Marker::Raw + Send + Sync>>,
+}
+
+impl<Marker, Layers> Bindings<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    pub fn new() -> Self {
+        Self {
+            layers: HashMap::new(),
+            stats: HashMap::new(),
+        }
+    }
+
+    pub fn bind_layer<M, I>(&mut self, name: impl Into<String>) -> &mut Self
+    where
+        M: Modifier<Target = Marker>,
+        Layers: Selector<M, I>,
+    {
+        self.layers
+            .insert(name.into(), |layers| M::combine(layers.select()));
+        self
+    }
+
+    /// Binds `name` to a value read on every evaluation, typically from another stat.
+    pub fn bind_stat(
+        &mut self,
+        name: impl Into<String>,
+        f: impl Fn() -> Marker::Raw + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.stats.insert(name.into(), Arc::new(f));
+        self
+    }
+}
+
+impl<Marker, Layers> Default for Bindings<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+enum Compiled<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    Constant(Marker::Raw),
+    Base,
+    Layer(fn(&Layers) -> Marker::Raw),
+    Stat(Arc<dyn Fn() -> Marker::Raw + Send + Sync>),
+    Add(Box<Compiled<Marker, Layers>>, Box<Compiled<Marker, Layers>>),
+    Sub(Box<Compiled<Marker, Layers>>, Box<Compiled<Marker, Layers>>),
+    Mul(Box<Compiled<Marker, Layers>>, Box<Compiled<Marker, Layers>>),
+    Div(Box<Compiled<Marker, Layers>>, Box<Compiled<Marker, Layers>>),
+}
+
+impl<Marker, Layers> Compiled<Marker, Layers>
+where
+    Marker: StatMarker,
+    Marker::Raw: Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+{
+    fn eval(&self, base: Marker::Raw, layers: &Layers) -> Marker::Raw {
+        match self {
+            Compiled::Constant(value) => *value,
+            Compiled::Base => base,
+            Compiled::Layer(combine) => combine(layers),
+            Compiled::Stat(f) => f(),
+            Compiled::Add(a, b) => a.eval(base, layers) + b.eval(base, layers),
+            Compiled::Sub(a, b) => a.eval(base, layers) - b.eval(base, layers),
+            Compiled::Mul(a, b) => a.eval(base, layers) * b.eval(base, layers),
+            Compiled::Div(a, b) => a.eval(base, layers) / b.eval(base, layers),
+        }
+    }
+}
+
+/// [`Expression`] with its variables resolved, usable as the formula of a stat.
+pub struct CompiledExpression<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    root: Arc<Compiled<Marker, Layers>>,
+}
+
+impl<Marker, Layers> Clone for CompiledExpression<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl<Marker, Layers> fmt::Debug for CompiledExpression<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompiledExpression").finish_non_exhaustive()
+    }
+}
+
+impl<Marker, Layers> StatFormula<Marker::Raw, Layers> for CompiledExpression<Marker, Layers>
+where
+    Marker: StatMarker,
+    Marker::Raw: Sub<Output = Marker::Raw> + Div<Output = Marker::Raw>,
+{
+    fn eval(&self, base: Marker::Raw, layers: &Layers) -> Marker::Raw {
+        self.root.eval(base, layers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        formula::DynStatFormula,
+        modifier::standard::{Additive, Flat, Multiplicative, PostAdditive},
+        send::standard::StandardStatS,
+        stat::Stat,
+    };
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct DummyMarker;
+
+    impl StatMarker for DummyMarker {
+        type Raw = f32;
+    }
+
+    type Flat32 = Flat<DummyMarker, f32, ()>;
+    type More32 = Multiplicative<DummyMarker, f32, ()>;
+    type Post32 = PostAdditive<DummyMarker, f32, ()>;
+    type Layers = crate::layers![Flat32, Additive<DummyMarker, f32, ()>, More32, Post32];
+
+    #[test]
+    fn test_eval() {
+        let expression = Expression::parse("(base + flat) * (1 + inc) * more + post").unwrap();
+
+        assert_eq!(
+            vec!["base", "flat", "inc", "more", "post"],
+            expression.variables()
+        );
+
+        let strength = Arc::new(StandardStatS::<DummyMarker, ()>::new(3.));
+        let mut bindings = Bindings::<DummyMarker, Layers>::new();
+        bindings
+            .bind_layer::<Flat32, _>("flat")
+            .bind_layer::<More32, _>("more")
+            .bind_layer::<Post32, _>("post")
+            .bind_stat("inc", {
+                let strength = strength.clone();
+                move || strength.get()
+            });
+        let formula = expression.compile(&bindings).unwrap();
+
+        let mut stat = Stat::<DummyMarker, Layers, _>::from_formula(10., formula);
+        stat.apply(Flat::from_raw(5.));
+        stat.apply(Multiplicative::from_raw(2.));
+        stat.apply(PostAdditive::from_raw(1.));
+
+        assert_eq!(121., stat.get());
+
+        strength.apply_flat(Flat::from_raw(1.));
+
+        assert_eq!(151., stat.get());
+    }
+
+    #[test]
+    fn test_dyn_formula() {
+        let mut bindings = Bindings::<DummyMarker, Layers>::new();
+        bindings.bind_layer::<Flat32, _>("flat");
+        let formula = Expression::parse("base * 2 + flat")
+            .unwrap()
+            .compile(&bindings)
+            .unwrap();
+
+        let mut stat =
+            Stat::<DummyMarker, Layers, DynStatFormula<f32, Layers>>::new(1.5, Box::new(formula));
+        stat.apply(Flat::from_raw(0.5));
+
+        assert_eq!(3.5, stat.get());
+    }
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct PreciseMarker;
+
+    impl StatMarker for PreciseMarker {
+        type Raw = f64;
+    }
+
+    #[test]
+    fn test_operators() {
+        type PreciseLayers = crate::layers![Flat<PreciseMarker, f64, ()>];
+
+        let bindings = Bindings::<PreciseMarker, PreciseLayers>::new();
+        let eval = |source: &str| {
+            let formula = Expression::parse(source)
+                .unwrap()
+                .compile(&bindings)
+                .unwrap();
+            Stat::<PreciseMarker, PreciseLayers, _>::from_formula(8., formula).get()
+        };
+
+        assert_eq!(0.1, eval("0.1"));
+        assert_eq!(-4.5, eval("-base / 2 + 1 - 1.5 * -1 - 3"));
+        assert_eq!(2., eval("base - 2 - 4"));
+        assert_eq!(1., eval("base / 4 / 2"));
+        assert_eq!(-8., eval("--(-base)"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(Error::Syntax {
+                position: 12,
+                expected: "`)`"
+            }),
+            Expression::parse("(base + flat").map(|_| ())
+        );
+        assert_eq!(
+            Err(Error::Syntax {
+                position: 7,
+                expected: "a number, variable or `(`"
+            }),
+            Expression::parse("base + ").map(|_| ())
+        );
+        assert_eq!(
+            Err(Error::Syntax {
+                position: 5,
+                expected: "an operator or end of input"
+            }),
+            Expression::parse("base ^ 1").map(|_| ())
+        );
+        assert_eq!(
+            Err(Error::TooDeep { position: 256 }),
+            Expression::parse(&"(".repeat(300)).map(|_| ())
+        );
+        assert_eq!(
+            Err(Error::TooDeep { position: 513 }),
+            Expression::parse(&"1+".repeat(1000)).map(|_| ())
+        );
+        assert_eq!(
+            Err(Error::UnknownVariable { position: 7 }),
+            Expression::parse("base + missing")
+                .unwrap()
+                .compile(&Bindings::<DummyMarker, Layers>::new())
+                .map(|_| ())
+        );
+    }
+}
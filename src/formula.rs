@@ -0,0 +1,180 @@
+use std::{any::Any, collections::HashMap, ops::Deref, sync::Arc};
+
+use crate::layer::{HCons, HNil};
+
+/// Shared handle to a stat formula. Cloning it only bumps a reference count, so any
+/// number of stats can use the same formula.
+pub struct Formula<F>
+where
+    F: ?Sized,
+{
+    name: Option<Arc<str>>,
+    f: Arc<F>,
+}
+
+impl<F> Formula<F>
+where
+    F: ?Sized,
+{
+    pub fn anonymous(f: Arc<F>) -> Self {
+        Self { name: None, f }
+    }
+
+    pub fn named(name: impl Into<Arc<str>>, f: Arc<F>) -> Self {
+        Self {
+            name: Some(name.into()),
+            f,
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn renamed(&self, name: impl Into<Arc<str>>) -> Self {
+        Self::named(name, self.f.clone())
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.f, &other.f)
+    }
+}
+
+impl<F> Clone for Formula<F>
+where
+    F: ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<F> Deref for Formula<F>
+where
+    F: ?Sized,
+{
+    type Target = F;
+
+    fn deref(&self) -> &Self::Target {
+        &self.f
+    }
+}
+
+/// Formula evaluated by a stat from its base value and its list of modifier layers.
+///
+/// Implemented for closures and [`Formula`] handles taking up to eight layers as separate
+/// arguments, and for formula types such as
+/// [`StandardFormula`](crate::modifier::standard::StandardFormula), which let `get` inline
+/// and can take any number of layers.
+pub trait StatFormula<Raw, Layers> {
+    fn eval(&self, base: Raw, layers: &Layers) -> Raw;
+}
+
+macro_rules! hlist {
+    () => { HNil };
+    ($h:ident $(, $t:ident)*) => { HCons<$h, hlist!($($t),*)> };
+}
+
+macro_rules! hlist_pat {
+    () => { HNil };
+    ($h:ident $(, $t:ident)*) => { HCons { head: $h, tail: hlist_pat!($($t),*) } };
+}
+
+macro_rules! impl_stat_formula {
+    ($($m:ident $v:ident),+) => {
+        impl<F, Raw, $($m),+> StatFormula<Raw, hlist!($($m),+)> for F
+        where
+            F: Fn(Raw, $(&$m),+) -> Raw,
+        {
+            fn eval(&self, base: Raw, layers: &hlist!($($m),+)) -> Raw {
+                let hlist_pat!($($v),+) = layers;
+                self(base, $($v),+)
+            }
+        }
+
+        impl<G, Raw, $($m),+> StatFormula<Raw, hlist!($($m),+)> for Formula<G>
+        where
+            G: ?Sized + Fn(Raw, $(&$m),+) -> Raw,
+        {
+            fn eval(&self, base: Raw, layers: &hlist!($($m),+)) -> Raw {
+                let hlist_pat!($($v),+) = layers;
+                (self.f)(base, $($v),+)
+            }
+        }
+    };
+}
+
+impl_stat_formula!(M1 m1);
+impl_stat_formula!(M1 m1, M2 m2);
+impl_stat_formula!(M1 m1, M2 m2, M3 m3);
+impl_stat_formula!(M1 m1, M2 m2, M3 m3, M4 m4);
+impl_stat_formula!(M1 m1, M2 m2, M3 m3, M4 m4, M5 m5);
+impl_stat_formula!(M1 m1, M2 m2, M3 m3, M4 m4, M5 m5, M6 m6);
+impl_stat_formula!(M1 m1, M2 m2, M3 m3, M4 m4, M5 m5, M6 m6, M7 m7);
+impl_stat_formula!(M1 m1, M2 m2, M3 m3, M4 m4, M5 m5, M6 m6, M7 m7, M8 m8);
+
+/// Formula picked at runtime, such as a [`Pipeline`](crate::pipeline::Pipeline) or a
+/// compiled [`Expression`](crate::expr::Expression), for any number of layers.
+pub type DynStatFormula<Raw, Layers> = Formula<dyn StatFormula<Raw, Layers> + Send + Sync>;
+
+impl<Raw, Layers> StatFormula<Raw, Layers> for DynStatFormula<Raw, Layers> {
+    fn eval(&self, base: Raw, layers: &Layers) -> Raw {
+        self.f.eval(base, layers)
+    }
+}
+
+/// Stat type whose formula can be stored in a [`FormulaRegistry`].
+pub trait Formulated {
+    type Formula: ?Sized + Send + Sync + 'static;
+}
+
+/// Formulas registered once under a name and shared by every stat built from them.
+#[derive(Default)]
+pub struct FormulaRegistry {
+    formulas: HashMap<Arc<str>, Box<dyn Any + Send + Sync>>,
+}
+
+impl FormulaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `f` as the formula of stats of type `S`, replacing any formula already
+    /// registered under `name`.
+    pub fn register<S>(
+        &mut self,
+        name: impl Into<Arc<str>>,
+        f: Box<S::Formula>,
+    ) -> Formula<S::Formula>
+    where
+        S: Formulated,
+    {
+        let name = name.into();
+        let formula: Formula<S::Formula> = Formula::named(name.clone(), Arc::from(f));
+        self.formulas.insert(name, Box::new(formula.clone()));
+        formula
+    }
+
+    /// Looks up the formula registered under `name`, if it was registered for stats of
+    /// type `S`.
+    pub fn get<S>(&self, name: &str) -> Option<Formula<S::Formula>>
+    where
+        S: Formulated,
+    {
+        self.formulas
+            .get(name)?
+            .downcast_ref::<Formula<S::Formula>>()
+            .cloned()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.formulas.contains_key(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.formulas.remove(name).is_some()
+    }
+}
@@ -0,0 +1,164 @@
+use std::{fmt, marker::PhantomData};
+
+use smallvec::{Array, SmallVec};
+
+use crate::{modifier::Modifier, raw::Finite, stat::StatMarker};
+
+/// One modifier layer of a stat.
+pub type Layer<M, const N: usize = 2> = SmallVec<[M; N]>;
+
+/// Heterogeneous list of modifier layers, written with [`layers!`](crate::layers).
+#[derive(PartialEq, Clone, Default)]
+pub struct HCons<H, T> {
+    pub head: H,
+    pub tail: T,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+pub struct HNil;
+
+/// Type of a list of modifier layers: `layers![M1, M2]` or, with inline capacity,
+/// `layers![M1, M2; 4]`.
+#[macro_export]
+macro_rules! layers {
+    (; $n:tt) => { $crate::layer::HNil };
+    ($m:ty $(, $rest:ty)*; $n:tt) => {
+        $crate::layer::HCons<$crate::layer::Layer<$m, $n>, $crate::layers![$($rest),*; $n]>
+    };
+    ($($m:ty),+ $(,)?) => { $crate::layers![$($m),+; 2] };
+}
+
+/// List type with the first layers given and the rest `T`, used to implement methods for a
+/// fixed layer position.
+macro_rules! prefixed {
+    ([], $rest:ty) => { $rest };
+    ([$h:ident $(, $t:ident)*], $rest:ty) => { $crate::layer::HCons<$h, $crate::layer::prefixed!([$($t),*], $rest)> };
+}
+
+pub(crate) use prefixed;
+
+/// Position of a layer in a list, inferred when selecting a layer by its modifier type.
+pub struct Here;
+
+pub struct There<I>(PhantomData<I>);
+
+pub trait LayerList: Default {
+    const LEN: usize;
+}
+
+impl LayerList for HNil {
+    const LEN: usize = 0;
+}
+
+impl<H, T> LayerList for HCons<H, T>
+where
+    H: Default,
+    T: LayerList,
+{
+    const LEN: usize = T::LEN + 1;
+}
+
+/// Layer list holding a layer of modifiers `M` at position `I`.
+pub trait Selector<M, I> {
+    type Array: Array<Item = M>;
+
+    const INDEX: usize;
+
+    fn select(&self) -> &SmallVec<Self::Array>;
+
+    fn select_mut(&mut self) -> &mut SmallVec<Self::Array>;
+}
+
+impl<M, T, const N: usize> Selector<M, Here> for HCons<Layer<M, N>, T> {
+    type Array = [M; N];
+
+    const INDEX: usize = 0;
+
+    fn select(&self) -> &SmallVec<Self::Array> {
+        &self.head
+    }
+
+    fn select_mut(&mut self) -> &mut SmallVec<Self::Array> {
+        &mut self.head
+    }
+}
+
+impl<M, I, H, T> Selector<M, There<I>> for HCons<H, T>
+where
+    T: Selector<M, I>,
+{
+    type Array = T::Array;
+
+    const INDEX: usize = T::INDEX + 1;
+
+    fn select(&self) -> &SmallVec<Self::Array> {
+        self.tail.select()
+    }
+
+    fn select_mut(&mut self) -> &mut SmallVec<Self::Array> {
+        self.tail.select_mut()
+    }
+}
+
+/// Layer list whose combined layers can be checked for NaN and infinities.
+pub trait FiniteLayers {
+    /// Position of the first layer combining to a non-finite value.
+    fn non_finite(&self) -> Option<usize>;
+}
+
+impl FiniteLayers for HNil {
+    fn non_finite(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<M, T, const N: usize> FiniteLayers for HCons<Layer<M, N>, T>
+where
+    M: Modifier,
+    <M::Target as StatMarker>::Raw: Finite,
+    T: FiniteLayers,
+{
+    fn non_finite(&self) -> Option<usize> {
+        if !M::combine(&self.head).is_finite() {
+            return Some(0);
+        }
+        self.tail.non_finite().map(|i| i + 1)
+    }
+}
+
+pub trait DebugLayers {
+    fn entries(&self, list: &mut fmt::DebugList<'_, '_>);
+}
+
+impl DebugLayers for HNil {
+    fn entries(&self, _: &mut fmt::DebugList<'_, '_>) {}
+}
+
+impl<H, T> DebugLayers for HCons<H, T>
+where
+    H: fmt::Debug,
+    T: DebugLayers,
+{
+    fn entries(&self, list: &mut fmt::DebugList<'_, '_>) {
+        list.entry(&self.head);
+        self.tail.entries(list);
+    }
+}
+
+impl fmt::Debug for HNil {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().finish()
+    }
+}
+
+impl<H, T> fmt::Debug for HCons<H, T>
+where
+    H: fmt::Debug,
+    T: DebugLayers,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        self.entries(&mut list);
+        list.finish()
+    }
+}
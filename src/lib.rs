@@ -1,21 +1,57 @@
 #![allow(clippy::type_complexity)]
 
+extern crate self as maxstat;
+
+pub mod access;
+pub mod block;
+pub mod cell;
+mod define;
+#[cfg(feature = "serde")]
+pub mod definition;
+pub mod error;
+pub mod expr;
+pub mod formula;
+pub mod layer;
 pub mod modifier;
 pub mod non_send;
+pub mod pipeline;
+pub mod raw;
 pub mod send;
+#[cfg(feature = "serde")]
+mod serialize;
 pub mod stat;
+pub mod transaction;
+
+#[doc(hidden)]
+pub use paste::paste as __paste;
 
 pub mod prelude {
-    pub use crate::stat::StatMarker;
+    pub use crate::access::StatAccess;
+    pub use crate::block::{Breakdown, DynStat, StatBlock};
+    pub use crate::error::Error;
+    pub use crate::expr::{Bindings, Expression};
+    pub use crate::formula::{DynStatFormula, Formula, FormulaRegistry};
     pub use crate::modifier::Modifier;
-    
+    pub use crate::pipeline::{Pipeline, PipelineBuilder};
+    pub use crate::stat::StatMarker;
+    pub use crate::transaction::StatSet;
+
     pub use crate::modifier::standard::Additive;
     pub use crate::modifier::standard::Flat;
     pub use crate::modifier::standard::Multiplicative;
     pub use crate::modifier::standard::PostAdditive;
     pub use crate::modifier::standard::PostMultiplicative;
+    pub use crate::modifier::standard::StandardFormula;
+
+    pub use crate::raw::AtomicRaw;
+    pub use crate::raw::Finite;
+    pub use crate::raw::Interval;
+    pub use crate::raw::Range;
+    pub use crate::raw::StandardRaw;
+    pub use crate::raw::Summation;
+    pub use crate::raw::{Vec2, Vec3, Vec4, Vector};
 
     pub use crate::non_send::standard::StandardStatNS;
 
-    pub use crate::send::standard::StandardStatS;
+    pub use crate::send::standard::{StandardStatCS, StandardStatRS, StandardStatS};
 }
@@ -1,10 +1,11 @@
-use std::marker::PhantomData;
+use std::{fmt, marker::PhantomData};
 
-use crate::stat::StatMarker;
+use crate::{formula::StatFormula, layers, raw::StandardRaw, stat::StatMarker};
 
 use super::Modifier;
 
 #[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Flat<Marker, Raw, Metadata>
 where
     Marker: StatMarker,
@@ -13,6 +14,7 @@ where
 {
     raw: Raw,
     metadata: Option<Metadata>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _p: PhantomData<Marker>,
 }
 
@@ -36,33 +38,35 @@ where
     }
 }
 
-impl<Marker, Metadata> Modifier for Flat<Marker, f32, Metadata>
+impl<Marker, Raw, Metadata> Flat<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f32>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
-    type Target = Marker;
-
-    fn from_raw(raw: <<Self as Modifier>::Target as StatMarker>::Raw) -> Self {
-        Self {
-            raw,
-            metadata: None,
-            _p: PhantomData,
-        }
-    }
-
-    fn raw(&self) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        self.raw
+    pub fn from_scalar(scalar: Raw::Scalar) -> Self {
+        Self::from_raw(Raw::splat(scalar))
     }
+}
 
-    fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(0., |acc, m| acc + m.raw)
+impl<Marker, Raw, Metadata> fmt::Debug for Flat<Marker, Raw, Metadata>
+where
+    Marker: StatMarker,
+    Raw: PartialEq + Clone + Copy + fmt::Debug,
+    Metadata: PartialEq + Clone + Copy + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Flat")
+            .field("raw", &self.raw)
+            .field("metadata", &self.metadata)
+            .finish()
     }
 }
 
-impl<Marker, Metadata> Modifier for Flat<Marker, f64, Metadata>
+impl<Marker, Raw, Metadata> Modifier for Flat<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f64>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
     type Target = Marker;
@@ -80,11 +84,12 @@ where
     }
 
     fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(0., |acc, m| acc + m.raw)
+        Marker::SUMMATION.sum(Raw::zero(), s, |m| m.raw)
     }
 }
 
 #[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Additive<Marker, Raw, Metadata>
 where
     Marker: StatMarker,
@@ -93,6 +98,7 @@ where
 {
     raw: Raw,
     metadata: Option<Metadata>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _p: PhantomData<Marker>,
 }
 
@@ -116,33 +122,35 @@ where
     }
 }
 
-impl<Marker, Metadata> Modifier for Additive<Marker, f32, Metadata>
+impl<Marker, Raw, Metadata> Additive<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f32>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
-    type Target = Marker;
-
-    fn from_raw(raw: <<Self as Modifier>::Target as StatMarker>::Raw) -> Self {
-        Self {
-            raw,
-            metadata: None,
-            _p: PhantomData,
-        }
-    }
-
-    fn raw(&self) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        self.raw
+    pub fn from_scalar(scalar: Raw::Scalar) -> Self {
+        Self::from_raw(Raw::splat(scalar))
     }
+}
 
-    fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(1., |acc, m| acc + m.raw)
+impl<Marker, Raw, Metadata> fmt::Debug for Additive<Marker, Raw, Metadata>
+where
+    Marker: StatMarker,
+    Raw: PartialEq + Clone + Copy + fmt::Debug,
+    Metadata: PartialEq + Clone + Copy + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Additive")
+            .field("raw", &self.raw)
+            .field("metadata", &self.metadata)
+            .finish()
     }
 }
 
-impl<Marker, Metadata> Modifier for Additive<Marker, f64, Metadata>
+impl<Marker, Raw, Metadata> Modifier for Additive<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f64>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
     type Target = Marker;
@@ -160,11 +168,12 @@ where
     }
 
     fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(1., |acc, m| acc + m.raw)
+        Marker::SUMMATION.sum(Raw::one(), s, |m| m.raw)
     }
 }
 
 #[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PostAdditive<Marker, Raw, Metadata>
 where
     Marker: StatMarker,
@@ -173,6 +182,7 @@ where
 {
     raw: Raw,
     metadata: Option<Metadata>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _p: PhantomData<Marker>,
 }
 
@@ -196,33 +206,35 @@ where
     }
 }
 
-impl<Marker, Metadata> Modifier for PostAdditive<Marker, f32, Metadata>
+impl<Marker, Raw, Metadata> PostAdditive<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f32>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
-    type Target = Marker;
-
-    fn from_raw(raw: <<Self as Modifier>::Target as StatMarker>::Raw) -> Self {
-        Self {
-            raw,
-            metadata: None,
-            _p: PhantomData,
-        }
-    }
-
-    fn raw(&self) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        self.raw
+    pub fn from_scalar(scalar: Raw::Scalar) -> Self {
+        Self::from_raw(Raw::splat(scalar))
     }
+}
 
-    fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(0., |acc, m| acc + m.raw)
+impl<Marker, Raw, Metadata> fmt::Debug for PostAdditive<Marker, Raw, Metadata>
+where
+    Marker: StatMarker,
+    Raw: PartialEq + Clone + Copy + fmt::Debug,
+    Metadata: PartialEq + Clone + Copy + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostAdditive")
+            .field("raw", &self.raw)
+            .field("metadata", &self.metadata)
+            .finish()
     }
 }
 
-impl<Marker, Metadata> Modifier for PostAdditive<Marker, f64, Metadata>
+impl<Marker, Raw, Metadata> Modifier for PostAdditive<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f64>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
     type Target = Marker;
@@ -240,11 +252,12 @@ where
     }
 
     fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(0., |acc, m| acc + m.raw)
+        Marker::SUMMATION.sum(Raw::zero(), s, |m| m.raw)
     }
 }
 
 #[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Multiplicative<Marker, Raw, Metadata>
 where
     Marker: StatMarker,
@@ -253,6 +266,7 @@ where
 {
     raw: Raw,
     metadata: Option<Metadata>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _p: PhantomData<Marker>,
 }
 
@@ -276,33 +290,35 @@ where
     }
 }
 
-impl<Marker, Metadata> Modifier for Multiplicative<Marker, f32, Metadata>
+impl<Marker, Raw, Metadata> Multiplicative<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f32>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
-    type Target = Marker;
-
-    fn from_raw(raw: <<Self as Modifier>::Target as StatMarker>::Raw) -> Self {
-        Self {
-            raw,
-            metadata: None,
-            _p: PhantomData,
-        }
-    }
-
-    fn raw(&self) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        self.raw
+    pub fn from_scalar(scalar: Raw::Scalar) -> Self {
+        Self::from_raw(Raw::splat(scalar))
     }
+}
 
-    fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(1., |acc, m| acc * m.raw)
+impl<Marker, Raw, Metadata> fmt::Debug for Multiplicative<Marker, Raw, Metadata>
+where
+    Marker: StatMarker,
+    Raw: PartialEq + Clone + Copy + fmt::Debug,
+    Metadata: PartialEq + Clone + Copy + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Multiplicative")
+            .field("raw", &self.raw)
+            .field("metadata", &self.metadata)
+            .finish()
     }
 }
 
-impl<Marker, Metadata> Modifier for Multiplicative<Marker, f64, Metadata>
+impl<Marker, Raw, Metadata> Modifier for Multiplicative<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f64>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
     type Target = Marker;
@@ -320,11 +336,12 @@ where
     }
 
     fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(1., |acc, m| acc * m.raw)
+        s.iter().fold(Raw::one(), |acc, m| acc * m.raw)
     }
 }
 
 #[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PostMultiplicative<Marker, Raw, Metadata>
 where
     Marker: StatMarker,
@@ -333,6 +350,7 @@ where
 {
     raw: Raw,
     metadata: Option<Metadata>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _p: PhantomData<Marker>,
 }
 
@@ -356,33 +374,35 @@ where
     }
 }
 
-impl<Marker, Metadata> Modifier for PostMultiplicative<Marker, f32, Metadata>
+impl<Marker, Raw, Metadata> PostMultiplicative<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f32>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
-    type Target = Marker;
-
-    fn from_raw(raw: <<Self as Modifier>::Target as StatMarker>::Raw) -> Self {
-        Self {
-            raw,
-            metadata: None,
-            _p: PhantomData,
-        }
-    }
-
-    fn raw(&self) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        self.raw
+    pub fn from_scalar(scalar: Raw::Scalar) -> Self {
+        Self::from_raw(Raw::splat(scalar))
     }
+}
 
-    fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(0., |acc, m| acc + m.raw)
+impl<Marker, Raw, Metadata> fmt::Debug for PostMultiplicative<Marker, Raw, Metadata>
+where
+    Marker: StatMarker,
+    Raw: PartialEq + Clone + Copy + fmt::Debug,
+    Metadata: PartialEq + Clone + Copy + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostMultiplicative")
+            .field("raw", &self.raw)
+            .field("metadata", &self.metadata)
+            .finish()
     }
 }
 
-impl<Marker, Metadata> Modifier for PostMultiplicative<Marker, f64, Metadata>
+impl<Marker, Raw, Metadata> Modifier for PostMultiplicative<Marker, Raw, Metadata>
 where
-    Marker: StatMarker<Raw = f64>,
+    Marker: StatMarker<Raw = Raw>,
+    Raw: StandardRaw,
     Metadata: PartialEq + Clone + Copy,
 {
     type Target = Marker;
@@ -400,6 +420,47 @@ where
     }
 
     fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-        s.iter().fold(0., |acc, m| acc + m.raw)
+        Marker::SUMMATION.sum(Raw::zero(), s, |m| m.raw)
+    }
+}
+
+/// Zero-sized formula of the standard stats:
+/// `((base + flat) * additive + post_add) * multiplicative + post_mul`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct StandardFormula;
+
+/// Modifier layers of the standard stats, in formula order.
+pub type StandardLayers<Marker, Metadata, const N: usize = 2> = layers![
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>;
+    N
+];
+
+impl<Marker, Metadata, const N: usize>
+    StatFormula<<Marker as StatMarker>::Raw, StandardLayers<Marker, Metadata, N>>
+    for StandardFormula
+where
+    Marker: StatMarker,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier<Target = Marker>,
+    Metadata: PartialEq + Clone + Copy,
+{
+    fn eval(
+        &self,
+        base: <Marker as StatMarker>::Raw,
+        layers: &StandardLayers<Marker, Metadata, N>,
+    ) -> <Marker as StatMarker>::Raw {
+        let post_add = &layers.tail.tail;
+        let post_mul = &post_add.tail.tail;
+        ((base + Flat::combine(&layers.head)) * Additive::combine(&layers.tail.head)
+            + PostAdditive::combine(&post_add.head))
+            * Multiplicative::combine(&post_add.tail.head)
+            + PostMultiplicative::combine(&post_mul.head)
     }
 }
@@ -1,232 +1,20 @@
-use std::{
-    cell::RefCell,
-    ops::{Add, Mul},
-};
-
-use crate::{
-    modifier::{
-        standard::{Additive, Flat, Multiplicative, PostAdditive, PostMultiplicative},
-        Modifier,
-    },
-    stat::{Stat5, StatMarker},
-};
-
-pub struct StandardStatNS<Marker, Metadata, const N: usize = 2>(
-    pub  RefCell<
-        Stat5<
-            Marker,
-            Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            N,
-        >,
-    >,
-)
-where
-    Marker: StatMarker,
-    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Metadata: PartialEq + Clone + Copy,
-    <Marker as StatMarker>::Raw:
-        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
-    <Marker as StatMarker>::Raw: Mul<
-        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Add<
-        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Mul<
-        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Add<
-        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >;
-
-impl<Marker, Metadata, const N: usize> StandardStatNS<Marker, Metadata, N>
-where
-    Marker: StatMarker,
-    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Metadata: PartialEq + Clone + Copy,
-    <Marker as StatMarker>::Raw:
-        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
-    <Marker as StatMarker>::Raw: Mul<
-        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Add<
-        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Mul<
-        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Add<
-        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >
-{
-    pub fn new(base: Marker::Raw) -> Self {
-        Self(RefCell::new(Stat5::new(
-            base,
-            Box::new(|b, m1, m2, m3, m4, m5| {
-                ((b + Flat::combine(m1)) * Additive::combine(m2) + PostAdditive::combine(m3))
-                    * Multiplicative::combine(m4)
-                    + PostMultiplicative::combine(m5)
-            }),
-        )))
-    }
-
-    pub fn apply_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.borrow_mut().apply_m1(value);
-    }
-
-    pub fn apply_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.borrow_mut().apply_m2(value);
-    }
-
-    pub fn apply_post_add(
-        &self,
-        value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) {
-        self.0.borrow_mut().apply_m3(value);
-    }
-
-    pub fn apply_multiplicative(
-        &self,
-        value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) {
-        self.0.borrow_mut().apply_m4(value);
-    }
-
-    pub fn apply_post_mul(
-        &self,
-        value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) {
-        self.0.borrow_mut().apply_m5(value);
-    }
-
-    pub fn remove_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.borrow_mut().remove_m1(value);
-    }
-
-    pub fn remove_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.borrow_mut().remove_m2(value);
-    }
+use crate::cell::{standard::StandardStat, WithRefCell};
 
-    pub fn remove_post_add(
-        &self,
-        value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) {
-        self.0.borrow_mut().remove_m3(value);
-    }
-
-    pub fn remove_multiplicative(
-        &self,
-        value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) {
-        self.0.borrow_mut().remove_m4(value);
-    }
-
-    pub fn remove_post_mul(
-        &self,
-        value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) {
-        self.0.borrow_mut().remove_m5(value);
-    }
-
-    pub fn has_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
-        self.0.borrow().has_m1(value)
-    }
-
-    pub fn has_additive(
-        &self,
-        value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) -> bool {
-        self.0.borrow().has_m2(value)
-    }
-
-    pub fn has_post_add(
-        &self,
-        value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) -> bool {
-        self.0.borrow().has_m3(value)
-    }
-
-    pub fn has_multiplicative(
-        &self,
-        value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) -> bool {
-        self.0.borrow().has_m4(value)
-    }
-
-    pub fn has_post_mul(
-        &self,
-        value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-    ) -> bool {
-        self.0.borrow().has_m5(value)
-    }
-
-    pub fn for_each_flat<F>(&self, f: F)
-    where
-        F: FnMut(&Flat<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.borrow().m1().iter().for_each(f);
-    }
-
-    pub fn for_each_additive<F>(&self, f: F)
-    where
-        F: FnMut(&Additive<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.borrow().m2().iter().for_each(f);
-    }
-
-    pub fn for_each_post_add<F>(&self, f: F)
-    where
-        F: FnMut(&PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.borrow().m3().iter().for_each(f);
-    }
-
-    pub fn for_each_multiplicative<F>(&self, f: F)
-    where
-        F: FnMut(&Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.borrow().m4().iter().for_each(f);
-    }
-
-    pub fn for_each_post_mul<F>(&self, f: F)
-    where
-        F: FnMut(&PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.borrow().m5().iter().for_each(f);
-    }
-
-    pub fn base(&self) -> Marker::Raw {
-        self.0.borrow().base()
-    }
-
-    pub fn get(&self) -> Marker::Raw {
-        self.0.borrow_mut().get()
-    }
-}
+/// [`StandardStat`] behind a `RefCell`, for single-threaded use.
+pub type StandardStatNS<Marker, Metadata, const N: usize = 2> =
+    StandardStat<Marker, Metadata, WithRefCell, N>;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        modifier::{
+            standard::{Additive, Flat, Multiplicative, PostAdditive, PostMultiplicative},
+            Modifier,
+        },
+        raw::Range,
+        stat::StatMarker,
+    };
 
     #[test]
     fn test_f32() {
@@ -389,4 +177,84 @@ mod tests {
 
         stat.for_each_flat(|f| println!("{:?}: +{}", f.metadata().unwrap(), f.raw()));
     }
+
+    #[test]
+    fn test_range() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
+
+        impl StatMarker for DummyMarker {
+            type Raw = Range<f32>;
+        }
+
+        let stat = StandardStatNS::<DummyMarker, ()>::new(Range::new(12., 18.));
+
+        stat.apply_flat(Flat::from_raw(Range::new(1., 3.)));
+        stat.apply_flat(Flat::from_scalar(2.));
+        stat.apply_additive(Additive::from_scalar(0.5));
+
+        let damage = stat.get();
+
+        assert_eq!(Range::new(22.5, 34.5), damage);
+        assert_eq!(28.5, damage.average());
+        assert_eq!(22.5, damage.sample(0.));
+        assert_eq!(34.5, damage.sample(1.));
+        assert_eq!(Range { min: 1, max: 5 }, Range::new(5, 1));
+        assert_eq!(
+            (3, 4),
+            (
+                Range::new(1_i32, 5).average(),
+                Range::new(1_u8, 5).sample(0.7)
+            )
+        );
+
+        let wide = Range::new(-100_i8, 100);
+
+        assert_eq!(
+            (0, -100, 0, 100),
+            (
+                wide.average(),
+                wide.sample(0.),
+                wide.sample(0.5),
+                wide.sample(1.)
+            )
+        );
+
+        let full = Range::new(i64::MIN, i64::MAX);
+
+        assert_eq!(
+            (-1, i64::MIN, i64::MAX),
+            (full.average(), full.sample(0.), full.sample(1.))
+        );
+
+        let full = Range::new(u64::MIN, u64::MAX);
+
+        assert_eq!((u64::MAX / 2, u64::MAX), (full.average(), full.sample(1.)));
+    }
+
+    #[test]
+    fn test_format() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
+
+        impl StatMarker for DummyMarker {
+            type Raw = f32;
+        }
+
+        let stat = StandardStatNS::<DummyMarker, ()>::new(10.);
+
+        stat.apply_flat(Flat::from_raw(5.));
+        stat.apply_multiplicative(Multiplicative::from_raw(2.));
+
+        let copy = stat.clone();
+        stat.apply_flat(Flat::from_raw(5.));
+
+        assert_eq!(30., copy.get());
+        assert_eq!(40., stat.get());
+        assert_eq!(
+            "((base 10 + flat 5) * additive 1 + post_add 0) * multiplicative 2 + post_mul 0 = 30",
+            copy.to_string()
+        );
+        assert!(format!("{copy:?}").contains("value: 30.0"));
+    }
 }
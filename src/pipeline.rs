@@ -0,0 +1,354 @@
+use std::{fmt, marker::PhantomData};
+
+use crate::{
+    error::Error,
+    formula::StatFormula,
+    layer::{LayerList, Selector},
+    modifier::Modifier,
+    stat::StatMarker,
+};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Op {
+    Add,
+    Mul,
+}
+
+/// One step of a [`Pipeline`]: the running value is added to or multiplied by a combined
+/// layer.
+pub struct Step<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    op: Op,
+    layer: usize,
+    name: &'static str,
+    combine: fn(&Layers) -> Marker::Raw,
+}
+
+impl<Marker, Layers> Step<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    pub fn op(&self) -> Op {
+        self.op
+    }
+
+    /// Layer position, numbered from `1` like the `mN` accessors.
+    pub fn layer(&self) -> usize {
+        self.layer
+    }
+
+    /// Modifier type name of the layer, without its path and generic arguments.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn combine(&self, layers: &Layers) -> Marker::Raw {
+        (self.combine)(layers)
+    }
+}
+
+impl<Marker, Layers> Clone for Step<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Marker, Layers> Copy for Step<Marker, Layers> where Marker: StatMarker {}
+
+impl<Marker, Layers> fmt::Debug for Step<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Step")
+            .field("op", &self.op)
+            .field("layer", &self.layer)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Unvalidated list of steps, turned into a [`Pipeline`] by [`build`](Self::build).
+///
+/// The standard formula is
+/// `Pipeline::builder().add_layer::<Flat<..>, _>().mul_layer::<Additive<..>, _>()` followed
+/// by the post-additive, multiplicative and post-multiplicative layers.
+pub struct PipelineBuilder<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    steps: Vec<Step<Marker, Layers>>,
+}
+
+impl<Marker, Layers> PipelineBuilder<Marker, Layers>
+where
+    Marker: StatMarker,
+    Layers: LayerList,
+{
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    pub fn add_layer<M, I>(self) -> Self
+    where
+        M: Modifier<Target = Marker>,
+        Layers: Selector<M, I>,
+    {
+        self.push::<M, I>(Op::Add)
+    }
+
+    pub fn mul_layer<M, I>(self) -> Self
+    where
+        M: Modifier<Target = Marker>,
+        Layers: Selector<M, I>,
+    {
+        self.push::<M, I>(Op::Mul)
+    }
+
+    fn push<M, I>(mut self, op: Op) -> Self
+    where
+        M: Modifier<Target = Marker>,
+        Layers: Selector<M, I>,
+    {
+        self.steps.push(Step {
+            op,
+            layer: <Layers as Selector<M, I>>::INDEX + 1,
+            name: short_type_name::<M>(),
+            combine: |layers| M::combine(layers.select()),
+        });
+        self
+    }
+
+    pub fn steps(&self) -> &[Step<Marker, Layers>] {
+        &self.steps
+    }
+
+    /// Checks that every layer is used exactly once, in the order of `Layers`.
+    pub fn build(self) -> Result<Pipeline<Marker, Layers>, Error> {
+        let mut used = vec![false; Layers::LEN];
+        let mut last = 0;
+        for step in &self.steps {
+            if std::mem::replace(&mut used[step.layer - 1], true) {
+                return Err(Error::DuplicateLayer { layer: step.layer });
+            }
+            if step.layer < last {
+                return Err(Error::LayerOrder { layer: step.layer });
+            }
+            last = step.layer;
+        }
+        if let Some(i) = used.iter().position(|&u| !u) {
+            return Err(Error::MissingLayer { layer: i + 1 });
+        }
+        Ok(Pipeline {
+            steps: self.steps,
+            _p: PhantomData,
+        })
+    }
+}
+
+/// Stat formula composed at runtime from the layers of `Layers`, applied in order to the
+/// base value. Built and validated by a [`PipelineBuilder`].
+pub struct Pipeline<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    steps: Vec<Step<Marker, Layers>>,
+    _p: PhantomData<Marker>,
+}
+
+impl<Marker, Layers> Pipeline<Marker, Layers>
+where
+    Marker: StatMarker,
+    Layers: LayerList,
+{
+    pub fn builder() -> PipelineBuilder<Marker, Layers> {
+        PipelineBuilder::new()
+    }
+
+    pub fn steps(&self) -> &[Step<Marker, Layers>] {
+        &self.steps
+    }
+
+    /// Running value after each step, for breakdowns.
+    pub fn trace(&self, base: Marker::Raw, layers: &Layers) -> Vec<Marker::Raw> {
+        self.steps
+            .iter()
+            .scan(base, |value, step| {
+                *value = apply(step, *value, layers);
+                Some(*value)
+            })
+            .collect()
+    }
+}
+
+fn apply<Marker, Layers>(
+    step: &Step<Marker, Layers>,
+    value: Marker::Raw,
+    layers: &Layers,
+) -> Marker::Raw
+where
+    Marker: StatMarker,
+{
+    match step.op {
+        Op::Add => value + step.combine(layers),
+        Op::Mul => value * step.combine(layers),
+    }
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+impl<Marker, Layers> Default for PipelineBuilder<Marker, Layers>
+where
+    Marker: StatMarker,
+    Layers: LayerList,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Marker, Layers> Clone for Pipeline<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn clone(&self) -> Self {
+        Self {
+            steps: self.steps.clone(),
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<Marker, Layers> Clone for PipelineBuilder<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn clone(&self) -> Self {
+        Self {
+            steps: self.steps.clone(),
+        }
+    }
+}
+
+impl<Marker, Layers> fmt::Debug for Pipeline<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.steps).finish()
+    }
+}
+
+impl<Marker, Layers> fmt::Debug for PipelineBuilder<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.steps).finish()
+    }
+}
+
+impl<Marker, Layers> StatFormula<Marker::Raw, Layers> for Pipeline<Marker, Layers>
+where
+    Marker: StatMarker,
+{
+    fn eval(&self, base: Marker::Raw, layers: &Layers) -> Marker::Raw {
+        self.steps
+            .iter()
+            .fold(base, |value, step| apply(step, value, layers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        modifier::standard::{
+            Additive, Flat, Multiplicative, PostAdditive, PostMultiplicative, StandardLayers,
+        },
+        stat::Stat,
+    };
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct DummyMarker;
+
+    impl StatMarker for DummyMarker {
+        type Raw = f32;
+    }
+
+    type Layers = StandardLayers<DummyMarker, ()>;
+
+    #[test]
+    fn test_standard() {
+        let pipeline = Pipeline::<DummyMarker, Layers>::builder()
+            .add_layer::<Flat<DummyMarker, f32, ()>, _>()
+            .mul_layer::<Additive<DummyMarker, f32, ()>, _>()
+            .add_layer::<PostAdditive<DummyMarker, f32, ()>, _>()
+            .mul_layer::<Multiplicative<DummyMarker, f32, ()>, _>()
+            .add_layer::<PostMultiplicative<DummyMarker, f32, ()>, _>()
+            .build()
+            .unwrap();
+
+        let mut stat = Stat::<DummyMarker, Layers, _>::from_formula(10., pipeline);
+        stat.apply(Flat::from_raw(5.));
+        stat.apply(Additive::from_raw(1.));
+        stat.apply(Multiplicative::from_raw(2.));
+        stat.apply(PostMultiplicative::from_raw(1.));
+
+        assert_eq!(61., stat.get());
+        assert_eq!(
+            vec![15., 30., 30., 60., 61.],
+            stat.formula().trace(stat.base(), stat.layers())
+        );
+        assert_eq!(
+            vec![
+                "Flat",
+                "Additive",
+                "PostAdditive",
+                "Multiplicative",
+                "PostMultiplicative"
+            ],
+            stat.formula()
+                .steps()
+                .iter()
+                .map(Step::name)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        let duplicate = Pipeline::<DummyMarker, Layers>::builder()
+            .add_layer::<Flat<DummyMarker, f32, ()>, _>()
+            .add_layer::<Flat<DummyMarker, f32, ()>, _>()
+            .build();
+
+        assert_eq!(
+            Err(Error::DuplicateLayer { layer: 1 }),
+            duplicate.map(|_| ())
+        );
+
+        let missing = Pipeline::<DummyMarker, Layers>::builder()
+            .add_layer::<Flat<DummyMarker, f32, ()>, _>()
+            .mul_layer::<Additive<DummyMarker, f32, ()>, _>()
+            .build();
+
+        assert_eq!(Err(Error::MissingLayer { layer: 3 }), missing.map(|_| ()));
+
+        let reversed = Pipeline::<DummyMarker, Layers>::builder()
+            .mul_layer::<Additive<DummyMarker, f32, ()>, _>()
+            .add_layer::<Flat<DummyMarker, f32, ()>, _>()
+            .build();
+
+        assert_eq!(Err(Error::LayerOrder { layer: 1 }), reversed.map(|_| ()));
+    }
+}
@@ -0,0 +1,198 @@
+use std::ops::{Add, Mul};
+
+pub mod interval;
+pub mod range;
+pub mod vector;
+
+pub use interval::Interval;
+pub use range::Range;
+pub use vector::{Vec2, Vec3, Vec4, Vector};
+
+/// Raw value type usable with the standard modifiers.
+pub trait StandardRaw: PartialEq + Clone + Copy + Add<Output = Self> + Mul<Output = Self> {
+    type Scalar: Copy;
+
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn splat(scalar: Self::Scalar) -> Self;
+
+    /// One step of compensated (Neumaier) summation. Types without a meaningful
+    /// rounding error keep the default plain addition.
+    fn compensated_add(sum: &mut Self, compensation: &mut Self, value: Self) {
+        let _ = compensation;
+        *sum = *sum + value;
+    }
+}
+
+/// Raw value that can be checked for NaN and infinities.
+pub trait Finite {
+    fn is_finite(&self) -> bool;
+}
+
+impl Finite for f32 {
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+}
+
+impl Finite for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+}
+
+macro_rules! impl_finite_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Finite for $t {
+                fn is_finite(&self) -> bool {
+                    true
+                }
+            }
+        )*
+    };
+}
+
+impl_finite_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Raw value that fits in 64 bits, so [`Cached`](crate::cell::Cached) stats can publish it
+/// in an atomic.
+pub trait AtomicRaw: Copy {
+    fn to_u64(self) -> u64;
+
+    fn from_u64(bits: u64) -> Self;
+}
+
+impl AtomicRaw for f32 {
+    fn to_u64(self) -> u64 {
+        self.to_bits().into()
+    }
+
+    fn from_u64(bits: u64) -> Self {
+        f32::from_bits(bits as u32)
+    }
+}
+
+impl AtomicRaw for f64 {
+    fn to_u64(self) -> u64 {
+        self.to_bits()
+    }
+
+    fn from_u64(bits: u64) -> Self {
+        f64::from_bits(bits)
+    }
+}
+
+macro_rules! impl_atomic_integer {
+    ($($t:ty),*) => {
+        $(
+            impl AtomicRaw for $t {
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+
+                fn from_u64(bits: u64) -> Self {
+                    bits as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// How the additive standard modifiers sum their values, selected per stat through
+/// [`StatMarker::SUMMATION`](crate::stat::StatMarker::SUMMATION).
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Summation {
+    #[default]
+    Naive,
+    Compensated,
+    Pairwise,
+}
+
+impl Summation {
+    pub fn sum<R, T>(self, init: R, values: &[T], raw: impl Fn(&T) -> R + Copy) -> R
+    where
+        R: StandardRaw,
+    {
+        match self {
+            Summation::Naive => values.iter().fold(init, |acc, v| acc + raw(v)),
+            Summation::Compensated => {
+                let mut sum = init;
+                let mut compensation = R::zero();
+                for v in values {
+                    R::compensated_add(&mut sum, &mut compensation, raw(v));
+                }
+                sum + compensation
+            }
+            Summation::Pairwise => init + pairwise(values, raw),
+        }
+    }
+}
+
+fn pairwise<R, T>(values: &[T], raw: impl Fn(&T) -> R + Copy) -> R
+where
+    R: StandardRaw,
+{
+    if values.len() <= 8 {
+        values.iter().fold(R::zero(), |acc, v| acc + raw(v))
+    } else {
+        let (left, right) = values.split_at(values.len() / 2);
+        pairwise(left, raw) + pairwise(right, raw)
+    }
+}
+
+impl StandardRaw for f32 {
+    type Scalar = f32;
+
+    fn zero() -> Self {
+        0.
+    }
+
+    fn one() -> Self {
+        1.
+    }
+
+    fn splat(scalar: Self::Scalar) -> Self {
+        scalar
+    }
+
+    fn compensated_add(sum: &mut Self, compensation: &mut Self, value: Self) {
+        let t = *sum + value;
+        if sum.abs() >= value.abs() {
+            *compensation += (*sum - t) + value;
+        } else {
+            *compensation += (value - t) + *sum;
+        }
+        *sum = t;
+    }
+}
+
+impl StandardRaw for f64 {
+    type Scalar = f64;
+
+    fn zero() -> Self {
+        0.
+    }
+
+    fn one() -> Self {
+        1.
+    }
+
+    fn splat(scalar: Self::Scalar) -> Self {
+        scalar
+    }
+
+    fn compensated_add(sum: &mut Self, compensation: &mut Self, value: Self) {
+        let t = *sum + value;
+        if sum.abs() >= value.abs() {
+            *compensation += (*sum - t) + value;
+        } else {
+            *compensation += (value - t) + *sum;
+        }
+        *sum = t;
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    fmt,
+    ops::{Add, Mul},
+};
+
+use super::{Finite, StandardRaw};
+
+/// Lower and upper bound of an uncertain value, propagated with interval arithmetic.
+///
+/// Use it as a marker's `Raw` to evaluate the possible spread of a stat: randomized
+/// modifiers are built with [`Interval::new`], conditional ones with [`Interval::either`]
+/// (with and without the modifier), and fixed ones with [`Interval::point`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "Bounds<T>",
+        bound(deserialize = "T: serde::Deserialize<'de> + PartialOrd + Copy")
+    )
+)]
+pub struct Interval<T> {
+    lo: T,
+    hi: T,
+}
+
+/// Deserialized form of an [`Interval`], ordered by [`Interval::new`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Bounds<T> {
+    lo: T,
+    hi: T,
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<Bounds<T>> for Interval<T>
+where
+    T: PartialOrd + Copy,
+{
+    fn from(bounds: Bounds<T>) -> Self {
+        Self::new(bounds.lo, bounds.hi)
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: PartialOrd + Copy,
+{
+    pub fn new(a: T, b: T) -> Self {
+        if b < a {
+            Self { lo: b, hi: a }
+        } else {
+            Self { lo: a, hi: b }
+        }
+    }
+
+    pub fn point(value: T) -> Self {
+        Self {
+            lo: value,
+            hi: value,
+        }
+    }
+
+    pub fn either(a: T, b: T) -> Self {
+        Self::new(a, b)
+    }
+
+    pub fn lo(&self) -> T {
+        self.lo
+    }
+
+    pub fn hi(&self) -> T {
+        self.hi
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.lo <= value && value <= self.hi
+    }
+
+    pub fn hull(&self, other: Self) -> Self {
+        Self {
+            lo: if other.lo < self.lo {
+                other.lo
+            } else {
+                self.lo
+            },
+            hi: if other.hi > self.hi {
+                other.hi
+            } else {
+                self.hi
+            },
+        }
+    }
+}
+
+impl<T> Add for Interval<T>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            lo: self.lo + rhs.lo,
+            hi: self.hi + rhs.hi,
+        }
+    }
+}
+
+impl<T> Mul for Interval<T>
+where
+    T: Mul<Output = T> + PartialOrd + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let products = [
+            self.lo * rhs.lo,
+            self.lo * rhs.hi,
+            self.hi * rhs.lo,
+            self.hi * rhs.hi,
+        ];
+        products[1..]
+            .iter()
+            .fold(Self::point(products[0]), |acc, &p| acc.hull(Self::point(p)))
+    }
+}
+
+impl<T> StandardRaw for Interval<T>
+where
+    T: StandardRaw + PartialOrd,
+{
+    type Scalar = T::Scalar;
+
+    fn zero() -> Self {
+        Self::point(T::zero())
+    }
+
+    fn one() -> Self {
+        Self::point(T::one())
+    }
+
+    fn splat(scalar: Self::Scalar) -> Self {
+        Self::point(T::splat(scalar))
+    }
+
+    fn compensated_add(sum: &mut Self, compensation: &mut Self, value: Self) {
+        T::compensated_add(&mut sum.lo, &mut compensation.lo, value.lo);
+        T::compensated_add(&mut sum.hi, &mut compensation.hi, value.hi);
+    }
+}
+
+impl<T> Finite for Interval<T>
+where
+    T: Finite,
+{
+    fn is_finite(&self) -> bool {
+        self.lo.is_finite() && self.hi.is_finite()
+    }
+}
+
+impl<T> fmt::Display for Interval<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
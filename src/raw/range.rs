@@ -0,0 +1,159 @@
+use std::{
+    fmt,
+    ops::{Add, Mul},
+};
+
+use super::{Finite, StandardRaw};
+
+/// Min–max raw value, e.g. weapon damage. Both ends are combined independently.
+///
+/// [`Range::new`] orders its ends; code setting the public fields directly must keep
+/// `min <= max`. Modifiers with negative factors may swap the ends of the result.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Range<T> {
+    pub min: T,
+    pub max: T,
+}
+
+impl<T> Range<T>
+where
+    T: PartialOrd + Copy,
+{
+    pub fn new(a: T, b: T) -> Self {
+        if b < a {
+            Self { min: b, max: a }
+        } else {
+            Self { min: a, max: b }
+        }
+    }
+}
+
+impl<T> Range<T>
+where
+    T: Copy,
+{
+    pub fn point(value: T) -> Self {
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+}
+
+macro_rules! impl_range_float {
+    ($($t:ty),*) => {
+        $(
+            impl Range<$t> {
+                pub fn average(&self) -> $t {
+                    (self.min + self.max) * 0.5
+                }
+
+                /// Maps `t` in `0..=1` (e.g. from the caller's RNG) onto the range.
+                pub fn sample(&self, t: $t) -> $t {
+                    self.min + (self.max - self.min) * t
+                }
+            }
+        )*
+    };
+}
+
+impl_range_float!(f32, f64);
+
+macro_rules! impl_range_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Range<$t> {
+                /// Midpoint of the range, rounded towards `min`.
+                pub fn average(&self) -> $t {
+                    (self.min as i128 + self.span() / 2) as $t
+                }
+
+                /// Maps `t` in `0..=1` (e.g. from the caller's RNG) onto the range, rounding
+                /// to the nearest value.
+                pub fn sample(&self, t: f64) -> $t {
+                    let span = self.span();
+                    let offset = ((span as f64 * t).round() as i128).clamp(0, span);
+                    (self.min as i128 + offset) as $t
+                }
+
+                /// `max - min` as an `i128`, which holds the span of any integer range.
+                fn span(&self) -> i128 {
+                    self.max as i128 - self.min as i128
+                }
+            }
+        )*
+    };
+}
+
+impl_range_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl<T> Add for Range<T>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            min: self.min + rhs.min,
+            max: self.max + rhs.max,
+        }
+    }
+}
+
+impl<T> Mul for Range<T>
+where
+    T: Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self {
+            min: self.min * rhs.min,
+            max: self.max * rhs.max,
+        }
+    }
+}
+
+impl<T> StandardRaw for Range<T>
+where
+    T: StandardRaw,
+{
+    type Scalar = T::Scalar;
+
+    fn zero() -> Self {
+        Self::point(T::zero())
+    }
+
+    fn one() -> Self {
+        Self::point(T::one())
+    }
+
+    fn splat(scalar: Self::Scalar) -> Self {
+        Self::point(T::splat(scalar))
+    }
+
+    fn compensated_add(sum: &mut Self, compensation: &mut Self, value: Self) {
+        T::compensated_add(&mut sum.min, &mut compensation.min, value.min);
+        T::compensated_add(&mut sum.max, &mut compensation.max, value.max);
+    }
+}
+
+impl<T> Finite for Range<T>
+where
+    T: Finite,
+{
+    fn is_finite(&self) -> bool {
+        self.min.is_finite() && self.max.is_finite()
+    }
+}
+
+impl<T> fmt::Display for Range<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}–{}", self.min, self.max)
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    fmt,
+    ops::{Add, Index, IndexMut, Mul},
+};
+
+use super::{Finite, StandardRaw};
+
+/// Fixed-size vector raw value, combined component-wise.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Vector<T, const D: usize>(pub [T; D]);
+
+pub type Vec2 = Vector<f32, 2>;
+pub type Vec3 = Vector<f32, 3>;
+pub type Vec4 = Vector<f32, 4>;
+
+impl<T, const D: usize> Vector<T, D>
+where
+    T: Copy,
+{
+    pub fn new(components: [T; D]) -> Self {
+        Self(components)
+    }
+
+    pub fn components(&self) -> [T; D] {
+        self.0
+    }
+
+    fn zip(self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+        let mut out = self.0;
+        for (o, r) in out.iter_mut().zip(other.0) {
+            *o = f(*o, r);
+        }
+        Self(out)
+    }
+}
+
+impl<T, const D: usize> From<[T; D]> for Vector<T, D> {
+    fn from(components: [T; D]) -> Self {
+        Self(components)
+    }
+}
+
+impl<T, const D: usize> fmt::Display for Vector<T, D>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{c}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T, const D: usize> Index<usize> for Vector<T, D> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const D: usize> IndexMut<usize> for Vector<T, D> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T, const D: usize> Add for Vector<T, D>
+where
+    T: Copy + Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip(rhs, |a, b| a + b)
+    }
+}
+
+impl<T, const D: usize> Mul for Vector<T, D>
+where
+    T: Copy + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.zip(rhs, |a, b| a * b)
+    }
+}
+
+impl<T, const D: usize> StandardRaw for Vector<T, D>
+where
+    T: StandardRaw,
+{
+    type Scalar = T::Scalar;
+
+    fn zero() -> Self {
+        Self([T::zero(); D])
+    }
+
+    fn one() -> Self {
+        Self([T::one(); D])
+    }
+
+    fn splat(scalar: Self::Scalar) -> Self {
+        Self([T::splat(scalar); D])
+    }
+
+    fn compensated_add(sum: &mut Self, compensation: &mut Self, value: Self) {
+        for i in 0..D {
+            T::compensated_add(&mut sum.0[i], &mut compensation.0[i], value.0[i]);
+        }
+    }
+}
+
+impl<T, const D: usize> Finite for Vector<T, D>
+where
+    T: Finite,
+{
+    fn is_finite(&self) -> bool {
+        self.0.iter().all(Finite::is_finite)
+    }
+}
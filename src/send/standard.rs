@@ -1,202 +1,30 @@
-use std::{
-    ops::{Add, Mul},
-    sync::Mutex,
-};
-
-use crate::{
-    modifier::{
-        standard::{Additive, Flat, Multiplicative, PostAdditive, PostMultiplicative},
-        Modifier,
-    },
-    stat::{Stat5, StatMarker},
-};
-
-pub struct StandardStatS<Marker, Metadata, const N: usize = 2>(
-    pub Mutex<
-        Stat5<
-            Marker,
-            Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
-            N,
-        >,
-    >,
-)
-where
-    Marker: StatMarker,
-    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Metadata: PartialEq + Clone + Copy,
-    <Marker as StatMarker>::Raw:
-        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
-    <Marker as StatMarker>::Raw: Mul<
-        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Add<
-        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Mul<
-        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Add<
-        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >;
-
-impl<Marker, Metadata, const N: usize> StandardStatS<Marker, Metadata, N>
-where
-    Marker: StatMarker,
-    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>: Modifier,
-    Metadata: PartialEq + Clone + Copy,
-    <Marker as StatMarker>::Raw:
-        Add<<<Flat<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw, Output = <Marker as StatMarker>::Raw>,
-    <Marker as StatMarker>::Raw: Mul<
-        <<Additive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Add<
-        <<PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Mul<
-        <<Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >,
-    <Marker as StatMarker>::Raw: Add<
-        <<PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata> as Modifier>::Target as StatMarker>::Raw,
-        Output = <Marker as StatMarker>::Raw,
-    >
-{
-    pub fn new(base: Marker::Raw) -> Self {
-        Self(Mutex::new(Stat5::new(
-            base,
-            Box::new(|b, m1, m2, m3, m4, m5| {
-                ((b + Flat::combine(m1)) * Additive::combine(m2) + PostAdditive::combine(m3))
-                    * Multiplicative::combine(m4)
-                    + PostMultiplicative::combine(m5)
-            }),
-        )))
-    }
-
-    pub fn apply_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().apply_m1(value);
-    }
-
-    pub fn apply_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().apply_m2(value);
-    }
-
-    pub fn apply_post_add(&self, value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().apply_m3(value);
-    }
-
-    pub fn apply_multiplicative(&self, value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().apply_m4(value);
-    }
-
-    pub fn apply_post_mul(&self, value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().apply_m5(value);
-    }
+use crate::cell::{standard::StandardStat, Cached, WithMutex, WithRwLock};
 
-    pub fn remove_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().remove_m1(value);
-    }
-
-    pub fn remove_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().remove_m2(value);
-    }
+/// [`StandardStat`] behind a mutex, so it can be shared between threads.
+pub type StandardStatS<Marker, Metadata, const N: usize = 2> =
+    StandardStat<Marker, Metadata, WithMutex, N>;
 
-    pub fn remove_post_add(&self, value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().remove_m3(value);
-    }
-
-    pub fn remove_multiplicative(&self, value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().remove_m4(value);
-    }
+/// [`StandardStat`] behind an `RwLock`, for stats read from many threads and rarely written.
+pub type StandardStatRS<Marker, Metadata, const N: usize = 2> =
+    StandardStat<Marker, Metadata, WithRwLock, N>;
 
-    pub fn remove_post_mul(&self, value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) {
-        self.0.lock().unwrap().remove_m5(value);
-    }
-
-    pub fn has_flat(&self, value: Flat<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
-        self.0.lock().unwrap().has_m1(value)
-    }
-
-    pub fn has_additive(&self, value: Additive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
-        self.0.lock().unwrap().has_m2(value)
-    }
-
-    pub fn has_post_add(&self, value: PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
-        self.0.lock().unwrap().has_m3(value)
-    }
-
-    pub fn has_multiplicative(&self, value: Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
-        self.0.lock().unwrap().has_m4(value)
-    }
-
-    pub fn has_post_mul(&self, value: PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>) -> bool {
-        self.0.lock().unwrap().has_m5(value)
-    }
-
-    pub fn for_each_flat<F>(&self, f: F)
-    where
-        F: FnMut(&Flat<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.lock().unwrap().m1().iter().for_each(f);
-    }
-
-    pub fn for_each_additive<F>(&self, f: F)
-    where
-        F: FnMut(&Additive<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.lock().unwrap().m2().iter().for_each(f);
-    }
-
-    pub fn for_each_post_add<F>(&self, f: F)
-    where
-        F: FnMut(&PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.lock().unwrap().m3().iter().for_each(f);
-    }
-
-    pub fn for_each_multiplicative<F>(&self, f: F)
-    where
-        F: FnMut(&Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.lock().unwrap().m4().iter().for_each(f);
-    }
-
-    pub fn for_each_post_mul<F>(&self, f: F)
-    where
-        F: FnMut(&PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>),
-    {
-        self.0.lock().unwrap().m5().iter().for_each(f);
-    }
-
-    pub fn base(&self) -> Marker::Raw {
-        self.0.lock().unwrap().base()
-    }
-
-    pub fn get(&self) -> Marker::Raw {
-        self.0.lock().unwrap().get()
-    }
-}
+/// [`StandardStatRS`] whose value is cached in an atomic, so `get` does not lock while no
+/// modifier changes. Only for `f32`, `f64` and integer raw types.
+pub type StandardStatCS<Marker, Metadata, const N: usize = 2> =
+    StandardStat<Marker, Metadata, Cached<WithRwLock>, N>;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        error::Error,
+        modifier::{
+            standard::{Additive, Flat, Multiplicative, PostAdditive, PostMultiplicative},
+            Modifier,
+        },
+        raw::{Interval, Summation, Vec3},
+        stat::StatMarker,
+    };
 
     #[test]
     fn test_f32() {
@@ -359,4 +187,270 @@ mod tests {
 
         stat.for_each_flat(|f| println!("{:?}: +{}", f.metadata().unwrap(), f.raw()));
     }
+
+    #[test]
+    fn test_vec3() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
+
+        impl StatMarker for DummyMarker {
+            type Raw = Vec3;
+        }
+
+        let stat = StandardStatS::<DummyMarker, ()>::new(Vec3::new([1., 2., 3.]));
+
+        stat.apply_flat(Flat::from_raw(Vec3::new([1., 0., -1.])));
+        stat.apply_additive(Additive::from_raw(Vec3::new([0.5, 0., 0.])));
+        stat.apply_multiplicative(Multiplicative::from_scalar(2.));
+        stat.apply_post_mul(PostMultiplicative::from_scalar(1.));
+
+        assert_eq!(Vec3::new([7., 5., 5.]), stat.get());
+    }
+
+    #[test]
+    fn test_interval() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
+
+        impl StatMarker for DummyMarker {
+            type Raw = Interval<f32>;
+        }
+
+        let stat = StandardStatS::<DummyMarker, ()>::new(Interval::point(100.));
+
+        stat.apply_flat(Flat::from_scalar(20.));
+        stat.apply_additive(Additive::from_raw(Interval::either(0., 0.25)));
+        stat.apply_multiplicative(Multiplicative::from_raw(Interval::new(1., 1.25)));
+
+        let attack = stat.get();
+
+        assert_eq!(120., attack.lo());
+        assert_eq!(187.5, attack.hi());
+        assert!(attack.contains(150.));
+    }
+
+    #[test]
+    fn test_compensated() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct NaiveMarker;
+
+        impl StatMarker for NaiveMarker {
+            type Raw = f32;
+        }
+
+        #[derive(PartialEq, Clone, Copy)]
+        struct CompensatedMarker;
+
+        impl StatMarker for CompensatedMarker {
+            type Raw = f32;
+
+            const SUMMATION: Summation = Summation::Compensated;
+        }
+
+        let naive = StandardStatS::<NaiveMarker, ()>::new(0.);
+        let compensated = StandardStatS::<CompensatedMarker, ()>::new(0.);
+
+        for raw in [1e8, 1., -1e8] {
+            naive.apply_flat(Flat::from_raw(raw));
+            compensated.apply_flat(Flat::from_raw(raw));
+        }
+
+        assert_eq!(0., naive.get());
+        assert_eq!(1., compensated.get());
+    }
+
+    #[test]
+    fn test_pairwise() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct NaiveMarker;
+
+        impl StatMarker for NaiveMarker {
+            type Raw = f32;
+        }
+
+        #[derive(PartialEq, Clone, Copy)]
+        struct PairwiseMarker;
+
+        impl StatMarker for PairwiseMarker {
+            type Raw = f32;
+
+            const SUMMATION: Summation = Summation::Pairwise;
+        }
+
+        let naive = StandardStatS::<NaiveMarker, ()>::new(0.);
+        let pairwise = StandardStatS::<PairwiseMarker, ()>::new(0.);
+
+        for _ in 0..10_000 {
+            naive.apply_flat(Flat::from_raw(0.1));
+            pairwise.apply_flat(Flat::from_raw(0.1));
+        }
+
+        let exact = 10_000. * f64::from(0.1f32);
+        let error = |value: f32| (f64::from(value) - exact).abs();
+
+        assert!(error(naive.get()) > 0.01);
+        assert!(error(pairwise.get()) < 0.001);
+    }
+
+    #[test]
+    fn test_non_finite() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
+
+        impl StatMarker for DummyMarker {
+            type Raw = f32;
+        }
+
+        let stat = StandardStatS::<DummyMarker, ()>::new(1.);
+
+        assert_eq!(
+            Err(Error::InvalidValue { layer: 1 }),
+            stat.try_apply_flat(Flat::from_raw(f32::NAN))
+        );
+        assert_eq!(1., stat.get());
+        assert_eq!(Ok(()), stat.try_apply_flat(Flat::from_raw(1.)));
+        assert_eq!(Ok(2.), stat.try_get());
+
+        stat.apply_multiplicative(Multiplicative::from_raw(f32::INFINITY));
+
+        assert_eq!(Err(Error::NonFinite { layer: Some(4) }), stat.try_get());
+
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let _guard = stat.write();
+                panic!("poison");
+            })
+            .join()
+            .unwrap_err();
+        });
+
+        assert_eq!(Err(Error::Poisoned), stat.try_has_flat(Flat::from_raw(1.)));
+        assert_eq!(Err(Error::Poisoned), stat.try_for_each_post_mul(|_| {}));
+        assert_eq!(Err(Error::Poisoned), stat.try_base());
+        assert_eq!(Err(Error::Poisoned), stat.try_breakdown().map(|_| ()));
+        assert_eq!(Err(Error::Poisoned), stat.try_clear_source(()));
+        assert_eq!(Err(Error::Poisoned), stat.try_clone().map(|_| ()));
+        assert_eq!(
+            "StandardStat(<stat lock poisoned by a panicking thread>)",
+            format!("{stat:?}")
+        );
+        assert_eq!(
+            "<stat lock poisoned by a panicking thread>",
+            stat.to_string()
+        );
+
+        stat.clear_poison();
+
+        assert_eq!(Ok(()), stat.try_clear());
+        assert_eq!(Ok(()), stat.try_set_base(3.));
+        assert_eq!(Ok(3.), stat.try_get());
+    }
+
+    #[test]
+    fn test_rw() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
+
+        impl StatMarker for DummyMarker {
+            type Raw = f32;
+        }
+
+        let stat = StandardStatRS::<DummyMarker, ()>::new(1.);
+        stat.apply_flat(Flat::from_raw(1.));
+
+        // Readers on other threads proceed while a read lock is held.
+        let _guard = stat.0.read().unwrap();
+        std::thread::scope(|s| {
+            let reader = s.spawn(|| {
+                let mut count = 0;
+                stat.for_each_flat(|_| count += 1);
+                (stat.get(), stat.has_flat(Flat::from_raw(1.)), count)
+            });
+            assert_eq!((2., true, 1), reader.join().unwrap());
+        });
+    }
+
+    #[test]
+    fn test_cached() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
+
+        impl StatMarker for DummyMarker {
+            type Raw = f64;
+        }
+
+        let mut stat = StandardStatCS::<DummyMarker, u8>::new(1.);
+        stat.apply_flat(*Flat::from_raw(2.).set_metadata(Some(1)));
+
+        assert_eq!(3., stat.get());
+        assert_eq!(3., stat.get());
+
+        stat.apply_multiplicative(Multiplicative::from_raw(2.));
+
+        assert_eq!(6., stat.get());
+
+        stat.clear_source(1);
+
+        assert_eq!(2., stat.get());
+
+        stat.get_mut().set_base(5.);
+
+        assert_eq!(10., stat.get());
+        assert_eq!(10., stat.clone().get());
+    }
+
+    #[test]
+    fn test_edit() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
+
+        impl StatMarker for DummyMarker {
+            type Raw = f32;
+        }
+
+        let stat = StandardStatS::<DummyMarker, ()>::new(10.);
+        stat.edit(|stat| {
+            stat.apply_m1(Flat::from_raw(5.));
+            stat.apply_m2(Additive::from_raw(0.5));
+            stat.apply_m4(Multiplicative::from_raw(2.));
+        });
+
+        assert_eq!(45., stat.get());
+
+        let mut guard = stat.write();
+        guard.remove_m4(Multiplicative::from_raw(2.));
+        guard.set_base(20.);
+        drop(guard);
+
+        assert_eq!(37.5, stat.get());
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive() {
+        #[derive(StatMarker, PartialEq, Clone, Copy)]
+        #[stat(raw = f32, name = "Health", default = 100., min = 0., max = 500.)]
+        struct Health;
+
+        #[derive(StatMarker, PartialEq, Clone, Copy)]
+        #[stat(raw = f64, summation = Compensated)]
+        struct Armor;
+
+        assert_eq!("Health", Health::name());
+        assert_eq!("Armor", Armor::name());
+        assert_eq!(Summation::Compensated, Armor::SUMMATION);
+
+        let stat = StandardStatS::<Health, ()>::default();
+
+        assert_eq!(100., stat.get());
+
+        stat.apply_multiplicative(Multiplicative::from_raw(9.));
+
+        assert_eq!(500., stat.get());
+
+        stat.apply_flat(Flat::from_raw(-200.));
+
+        assert_eq!(0., stat.get());
+        assert_eq!(0., StandardStatS::<Armor, ()>::default().get());
+    }
 }
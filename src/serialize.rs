@@ -0,0 +1,458 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::{self, SerializeStruct, SerializeTuple},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use smallvec::SmallVec;
+
+use crate::{
+    cell::{standard::StandardStat, CellPolicy},
+    formula::{Formula, FormulaRegistry, StatFormula},
+    layer::{HCons, HNil, LayerList},
+    modifier::standard::{
+        Additive, Flat, Multiplicative, PostAdditive, PostMultiplicative, StandardFormula,
+    },
+    raw::{StandardRaw, Vector},
+    stat::{Stat, Stat5, StatMarker},
+};
+
+impl<T, const D: usize> Serialize for Vector<T, D>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_tuple(D)?;
+        for component in &self.0 {
+            state.serialize_element(component)?;
+        }
+        state.end()
+    }
+}
+
+impl<'de, T, const D: usize> Deserialize<'de> for Vector<T, D>
+where
+    T: Deserialize<'de> + Copy + Default,
+{
+    fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+    where
+        De: Deserializer<'de>,
+    {
+        struct VectorVisitor<T, const D: usize>(PhantomData<T>);
+
+        impl<'de, T, const D: usize> Visitor<'de> for VectorVisitor<T, D>
+        where
+            T: Deserialize<'de> + Copy + Default,
+        {
+            type Value = Vector<T, D>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a vector of {D} components")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut components = [T::default(); D];
+                for (i, component) in components.iter_mut().enumerate() {
+                    *component = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+                Ok(Vector(components))
+            }
+        }
+
+        deserializer.deserialize_tuple(D, VectorVisitor(PhantomData))
+    }
+}
+
+trait SerializeLayers {
+    fn serialize_layers<S>(&self, state: &mut S) -> Result<(), S::Error>
+    where
+        S: SerializeTuple;
+}
+
+impl SerializeLayers for HNil {
+    fn serialize_layers<S>(&self, _: &mut S) -> Result<(), S::Error>
+    where
+        S: SerializeTuple,
+    {
+        Ok(())
+    }
+}
+
+impl<H, T> SerializeLayers for HCons<H, T>
+where
+    H: Serialize,
+    T: SerializeLayers,
+{
+    fn serialize_layers<S>(&self, state: &mut S) -> Result<(), S::Error>
+    where
+        S: SerializeTuple,
+    {
+        state.serialize_element(&self.head)?;
+        self.tail.serialize_layers(state)
+    }
+}
+
+impl Serialize for HNil {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_tuple(0)?.end()
+    }
+}
+
+impl<H, T> Serialize for HCons<H, T>
+where
+    Self: LayerList + SerializeLayers,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_tuple(Self::LEN)?;
+        self.serialize_layers(&mut state)?;
+        state.end()
+    }
+}
+
+trait DeserializeLayers<'de>: Sized {
+    fn deserialize_layers<A>(seq: &mut A, index: usize) -> Result<Self, A::Error>
+    where
+        A: SeqAccess<'de>;
+}
+
+impl<'de> DeserializeLayers<'de> for HNil {
+    fn deserialize_layers<A>(_: &mut A, _: usize) -> Result<Self, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Ok(HNil)
+    }
+}
+
+impl<'de, H, T> DeserializeLayers<'de> for HCons<H, T>
+where
+    H: Deserialize<'de>,
+    T: DeserializeLayers<'de>,
+{
+    fn deserialize_layers<A>(seq: &mut A, index: usize) -> Result<Self, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let head = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(index, &"more modifier layers"))?;
+        let tail = T::deserialize_layers(seq, index + 1)?;
+        Ok(HCons { head, tail })
+    }
+}
+
+struct LayersVisitor<L>(PhantomData<L>);
+
+impl<'de, L> Visitor<'de> for LayersVisitor<L>
+where
+    L: LayerList + DeserializeLayers<'de>,
+{
+    type Value = L;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} modifier layers", L::LEN)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        L::deserialize_layers(&mut seq, 0)
+    }
+}
+
+impl<'de> Deserialize<'de> for HNil {
+    fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+    where
+        De: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(0, LayersVisitor(PhantomData))
+    }
+}
+
+impl<'de, H, T> Deserialize<'de> for HCons<H, T>
+where
+    Self: LayerList + DeserializeLayers<'de>,
+{
+    fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+    where
+        De: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(Self::LEN, LayersVisitor(PhantomData))
+    }
+}
+
+impl<Marker, Layers, G> Serialize for Stat<Marker, Layers, Formula<G>>
+where
+    Marker: StatMarker,
+    Marker::Raw: Serialize,
+    Layers: LayerList + Serialize,
+    G: ?Sized,
+    Formula<G>: StatFormula<Marker::Raw, Layers>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Stat", 3)?;
+        state.serialize_field("formula", &self.formula_name())?;
+        state.serialize_field("base", &self.base())?;
+        state.serialize_field("layers", self.layers())?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct StatData<Raw, Layers> {
+    formula: Option<String>,
+    base: Raw,
+    layers: Layers,
+}
+
+impl<Marker, Layers, G> Stat<Marker, Layers, Formula<G>>
+where
+    Marker: StatMarker,
+    Layers: LayerList,
+    G: ?Sized + Send + Sync + 'static,
+    Formula<G>: StatFormula<Marker::Raw, Layers>,
+{
+    /// Deserializes base and modifiers, re-attaching the formula registered under the
+    /// serialized formula name.
+    pub fn deserialize_with<'de, De>(
+        deserializer: De,
+        registry: &FormulaRegistry,
+    ) -> Result<Self, De::Error>
+    where
+        De: Deserializer<'de>,
+        Marker::Raw: Deserialize<'de>,
+        Layers: Deserialize<'de>,
+    {
+        let data = StatData::<Marker::Raw, Layers>::deserialize(deserializer)?;
+        let name = data
+            .formula
+            .ok_or_else(|| de::Error::custom("stat has no named formula"))?;
+        let f = registry
+            .get::<Self>(&name)
+            .ok_or_else(|| de::Error::custom(format_args!("unknown formula `{name}`")))?;
+        Ok(Self::from_parts(data.base, data.layers, f))
+    }
+}
+
+type StandardStat5<Marker, Metadata, const N: usize> = Stat5<
+    Marker,
+    Flat<Marker, <Marker as StatMarker>::Raw, Metadata>,
+    Additive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+    PostAdditive<Marker, <Marker as StatMarker>::Raw, Metadata>,
+    Multiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+    PostMultiplicative<Marker, <Marker as StatMarker>::Raw, Metadata>,
+    N,
+    StandardFormula,
+>;
+
+fn serialize_standard<Marker, Metadata, const N: usize, S>(
+    stat: &StandardStat5<Marker, Metadata, N>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    Marker: StatMarker,
+    Marker::Raw: StandardRaw + Serialize,
+    Metadata: PartialEq + Clone + Copy + Serialize,
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct("StandardStat", 6)?;
+    state.serialize_field("base", &stat.base())?;
+    state.serialize_field("flat", stat.m1())?;
+    state.serialize_field("additive", stat.m2())?;
+    state.serialize_field("post_add", stat.m3())?;
+    state.serialize_field("multiplicative", stat.m4())?;
+    state.serialize_field("post_mul", stat.m5())?;
+    state.end()
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "Marker::Raw: Deserialize<'de>, Metadata: Deserialize<'de>"))]
+struct StandardStatData<Marker, Metadata, const N: usize>
+where
+    Marker: StatMarker,
+    Marker::Raw: StandardRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    base: Marker::Raw,
+    flat: SmallVec<[Flat<Marker, Marker::Raw, Metadata>; N]>,
+    additive: SmallVec<[Additive<Marker, Marker::Raw, Metadata>; N]>,
+    post_add: SmallVec<[PostAdditive<Marker, Marker::Raw, Metadata>; N]>,
+    multiplicative: SmallVec<[Multiplicative<Marker, Marker::Raw, Metadata>; N]>,
+    post_mul: SmallVec<[PostMultiplicative<Marker, Marker::Raw, Metadata>; N]>,
+}
+
+impl<Marker, Metadata, const N: usize> StandardStatData<Marker, Metadata, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: StandardRaw,
+    Metadata: PartialEq + Clone + Copy,
+{
+    fn apply_to(self, stat: &mut StandardStat5<Marker, Metadata, N>) {
+        self.flat.into_iter().for_each(|v| stat.apply_m1(v));
+        self.additive.into_iter().for_each(|v| stat.apply_m2(v));
+        self.post_add.into_iter().for_each(|v| stat.apply_m3(v));
+        self.multiplicative
+            .into_iter()
+            .for_each(|v| stat.apply_m4(v));
+        self.post_mul.into_iter().for_each(|v| stat.apply_m5(v));
+    }
+}
+
+impl<Marker, Metadata, P, const N: usize> Serialize for StandardStat<Marker, Metadata, P, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: StandardRaw + Serialize,
+    Metadata: PartialEq + Clone + Copy + Serialize,
+    P: CellPolicy,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let stat = P::try_read(&self.0).map_err(ser::Error::custom)?;
+        serialize_standard(&stat, serializer)
+    }
+}
+
+impl<'de, Marker, Metadata, P, const N: usize> Deserialize<'de>
+    for StandardStat<Marker, Metadata, P, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: StandardRaw + Deserialize<'de>,
+    Metadata: PartialEq + Clone + Copy + Deserialize<'de>,
+    P: CellPolicy,
+{
+    fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+    where
+        De: Deserializer<'de>,
+    {
+        let data = StandardStatData::<Marker, Metadata, N>::deserialize(deserializer)?;
+        let mut stat = Self::new(data.base);
+        data.apply_to(stat.get_mut());
+        Ok(stat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        modifier::Modifier, non_send::standard::StandardStatNS, raw::Interval,
+        send::standard::StandardStatS, stat::Stat1,
+    };
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct DummyMarker;
+
+    impl StatMarker for DummyMarker {
+        type Raw = f32;
+    }
+
+    #[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+    enum Source {
+        Sword,
+        Ring,
+    }
+
+    #[test]
+    fn test_standard_roundtrip() {
+        let stat = StandardStatS::<DummyMarker, Source>::new(10.);
+        stat.apply_flat(Flat::from_raw(5.).set_metadata(Some(Source::Sword)).build());
+        stat.apply_multiplicative(
+            Multiplicative::from_raw(2.)
+                .set_metadata(Some(Source::Ring))
+                .build(),
+        );
+
+        let json = serde_json::to_string(&stat).unwrap();
+        let loaded: StandardStatNS<DummyMarker, Source> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(30., loaded.get());
+        assert!(loaded.has_flat(Flat::from_raw(5.).set_metadata(Some(Source::Sword)).build()));
+    }
+
+    #[test]
+    fn test_poisoned() {
+        let stat = StandardStatS::<DummyMarker, Source>::new(10.);
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let _guard = stat.write();
+                panic!("poison");
+            })
+            .join()
+            .unwrap_err();
+        });
+
+        let error = serde_json::to_string(&stat).unwrap_err();
+
+        assert_eq!(
+            "stat lock poisoned by a panicking thread",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::new(1., 5.);
+        let json = serde_json::to_string(&interval).unwrap();
+
+        assert_eq!(
+            interval,
+            serde_json::from_str::<Interval<f32>>(&json).unwrap()
+        );
+        assert_eq!(
+            interval,
+            serde_json::from_str::<Interval<f32>>(r#"{"lo": 5, "hi": 1}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_formula_lookup() {
+        type Sum = Flat<DummyMarker, f32, Source>;
+
+        let mut registry = FormulaRegistry::new();
+        let sum = registry
+            .register::<Stat1<DummyMarker, Sum>>("sum", Box::new(|b, m1| b + Sum::combine(m1)));
+
+        let mut stat = Stat1::<DummyMarker, Sum>::from_formula(1., sum);
+        stat.apply_m1(Sum::from_raw(2.));
+
+        let json = serde_json::to_string(&stat).unwrap();
+
+        let loaded = Stat1::<DummyMarker, Sum>::deserialize_with(
+            &mut serde_json::Deserializer::from_str(&json),
+            &registry,
+        )
+        .unwrap();
+
+        assert_eq!(Some("sum"), loaded.formula_name());
+        assert!(loaded.formula().ptr_eq(stat.formula()));
+        assert_eq!(3., loaded.get());
+
+        let missing = Stat1::<DummyMarker, Sum>::deserialize_with(
+            &mut serde_json::Deserializer::from_str(&json),
+            &FormulaRegistry::new(),
+        );
+
+        assert!(missing.is_err());
+    }
+}
@@ -1,771 +1,201 @@
 use std::{
     cmp::PartialEq,
+    fmt,
     marker::PhantomData,
     ops::{Add, Mul},
+    sync::Arc,
 };
 
 use smallvec::SmallVec;
 
-use crate::modifier::Modifier;
-
-pub trait StatMarker: PartialEq + Clone + Copy {
-    type Raw: PartialEq + Clone + Copy + Add<Output = Self::Raw> + Mul<Output = Self::Raw>;
-}
-
-pub struct Stat1<Marker, M1, const N: usize = 2>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-{
-    base: Marker::Raw,
-    m1: SmallVec<[M1; N]>,
-    f: Box<dyn Fn(Marker::Raw, &SmallVec<[M1; N]>) -> Marker::Raw + Send>,
-    _p: PhantomData<Marker>,
-}
-
-impl<Marker, M1, const N: usize> Stat1<Marker, M1, N>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-{
-    pub fn new(
-        base: Marker::Raw,
-        f: Box<dyn Fn(Marker::Raw, &SmallVec<[M1; N]>) -> Marker::Raw + Send>,
-    ) -> Self {
-        Self {
-            base,
-            m1: SmallVec::new(),
-            f,
-            _p: PhantomData,
-        }
-    }
-
-    pub fn apply_m1(&mut self, value: M1) {
-        self.m1.push(value);
-    }
-
-    pub fn remove_m1(&mut self, value: M1) {
-        if let Some(i) = self.m1.iter().position(|&v| v == value) {
-            self.m1.swap_remove(i);
-        }
-    }
-
-    pub fn has_m1(&self, value: M1) -> bool {
-        self.m1.iter().any(|&v| v == value)
-    }
-
-    pub fn base(&self) -> Marker::Raw {
-        self.base
-    }
-
-    pub fn get(&self) -> Marker::Raw {
-        (self.f)(self.base, &self.m1)
-    }
-
-    pub fn m1(&self) -> &SmallVec<[M1; N]> {
-        &self.m1
-    }
-}
-
-pub struct Stat2<Marker, M1, M2, const N: usize = 2>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-{
-    base: Marker::Raw,
-    m1: SmallVec<[M1; N]>,
-    m2: SmallVec<[M2; N]>,
-    f: Box<dyn Fn(Marker::Raw, &SmallVec<[M1; N]>, &SmallVec<[M2; N]>) -> Marker::Raw + Send>,
-    _p: PhantomData<Marker>,
-}
-
-impl<Marker, M1, M2, const N: usize> Stat2<Marker, M1, M2, N>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-{
-    pub fn new(
-        base: Marker::Raw,
-        f: Box<dyn Fn(Marker::Raw, &SmallVec<[M1; N]>, &SmallVec<[M2; N]>) -> Marker::Raw + Send>,
-    ) -> Self {
-        Self {
-            base,
-            m1: SmallVec::new(),
-            m2: SmallVec::new(),
-            f,
-            _p: PhantomData,
-        }
-    }
-
-    pub fn apply_m1(&mut self, value: M1) {
-        self.m1.push(value);
-    }
-
-    pub fn apply_m2(&mut self, value: M2) {
-        self.m2.push(value);
-    }
-
-    pub fn remove_m1(&mut self, value: M1) {
-        if let Some(i) = self.m1.iter().position(|&v| v == value) {
-            self.m1.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m2(&mut self, value: M2) {
-        if let Some(i) = self.m2.iter().position(|&v| v == value) {
-            self.m2.swap_remove(i);
-        }
-    }
-
-    pub fn has_m1(&self, value: M1) -> bool {
-        self.m1.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m2(&self, value: M2) -> bool {
-        self.m2.iter().any(|&v| v == value)
-    }
-
-    pub fn base(&self) -> Marker::Raw {
-        self.base
-    }
-
-    pub fn get(&self) -> Marker::Raw {
-        (self.f)(self.base, &self.m1, &self.m2)
-    }
-
-    pub fn m1(&self) -> &SmallVec<[M1; N]> {
-        &self.m1
-    }
-
-    pub fn m2(&self) -> &SmallVec<[M2; N]> {
-        &self.m2
-    }
-}
-
-pub struct Stat3<Marker, M1, M2, M3, const N: usize = 2>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-{
-    base: Marker::Raw,
-    m1: SmallVec<[M1; N]>,
-    m2: SmallVec<[M2; N]>,
-    m3: SmallVec<[M3; N]>,
-    f: Box<
-        dyn Fn(
-                Marker::Raw,
-                &SmallVec<[M1; N]>,
-                &SmallVec<[M2; N]>,
-                &SmallVec<[M3; N]>,
-            ) -> Marker::Raw
-            + Send,
-    >,
-    _p: PhantomData<Marker>,
-}
-
-impl<Marker, M1, M2, M3, const N: usize> Stat3<Marker, M1, M2, M3, N>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-{
-    pub fn new(
-        base: Marker::Raw,
-        f: Box<
-            dyn Fn(
-                    Marker::Raw,
-                    &SmallVec<[M1; N]>,
-                    &SmallVec<[M2; N]>,
-                    &SmallVec<[M3; N]>,
-                ) -> Marker::Raw
-                + Send,
-        >,
-    ) -> Self {
-        Self {
-            base,
-            m1: SmallVec::new(),
-            m2: SmallVec::new(),
-            m3: SmallVec::new(),
-            f,
-            _p: PhantomData,
-        }
-    }
-
-    pub fn apply_m1(&mut self, value: M1) {
-        self.m1.push(value);
-    }
-
-    pub fn apply_m2(&mut self, value: M2) {
-        self.m2.push(value);
-    }
-
-    pub fn apply_m3(&mut self, value: M3) {
-        self.m3.push(value);
-    }
-
-    pub fn remove_m1(&mut self, value: M1) {
-        if let Some(i) = self.m1.iter().position(|&v| v == value) {
-            self.m1.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m2(&mut self, value: M2) {
-        if let Some(i) = self.m2.iter().position(|&v| v == value) {
-            self.m2.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m3(&mut self, value: M3) {
-        if let Some(i) = self.m3.iter().position(|&v| v == value) {
-            self.m3.swap_remove(i);
-        }
-    }
-
-    pub fn has_m1(&self, value: M1) -> bool {
-        self.m1.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m2(&self, value: M2) -> bool {
-        self.m2.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m3(&self, value: M3) -> bool {
-        self.m3.iter().any(|&v| v == value)
-    }
-
-    pub fn base(&self) -> Marker::Raw {
-        self.base
-    }
-
-    pub fn get(&self) -> Marker::Raw {
-        (self.f)(self.base, &self.m1, &self.m2, &self.m3)
-    }
-
-    pub fn m1(&self) -> &SmallVec<[M1; N]> {
-        &self.m1
-    }
-
-    pub fn m2(&self) -> &SmallVec<[M2; N]> {
-        &self.m2
-    }
-
-    pub fn m3(&self) -> &SmallVec<[M3; N]> {
-        &self.m3
-    }
-}
-
-pub struct Stat4<Marker, M1, M2, M3, M4, const N: usize = 2>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-{
-    base: Marker::Raw,
-    m1: SmallVec<[M1; N]>,
-    m2: SmallVec<[M2; N]>,
-    m3: SmallVec<[M3; N]>,
-    m4: SmallVec<[M4; N]>,
-    f: Box<
-        dyn Fn(
-            Marker::Raw,
-            &SmallVec<[M1; N]>,
-            &SmallVec<[M2; N]>,
-            &SmallVec<[M3; N]>,
-            &SmallVec<[M4; N]>,
-        ) -> Marker::Raw + Send,
-    >,
-    _p: PhantomData<Marker>,
-}
-
-impl<Marker, M1, M2, M3, M4, const N: usize> Stat4<Marker, M1, M2, M3, M4, N>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-{
-    pub fn new(
-        base: Marker::Raw,
-        f: Box<
-            dyn Fn(
-                Marker::Raw,
-                &SmallVec<[M1; N]>,
-                &SmallVec<[M2; N]>,
-                &SmallVec<[M3; N]>,
-                &SmallVec<[M4; N]>,
-            ) -> Marker::Raw + Send,
-        >,
-    ) -> Self {
-        Self {
-            base,
-            m1: SmallVec::new(),
-            m2: SmallVec::new(),
-            m3: SmallVec::new(),
-            m4: SmallVec::new(),
-            f,
-            _p: PhantomData,
-        }
-    }
-
-    pub fn apply_m1(&mut self, value: M1) {
-        self.m1.push(value);
-    }
-
-    pub fn apply_m2(&mut self, value: M2) {
-        self.m2.push(value);
-    }
-
-    pub fn apply_m3(&mut self, value: M3) {
-        self.m3.push(value);
-    }
-
-    pub fn apply_m4(&mut self, value: M4) {
-        self.m4.push(value);
-    }
-
-    pub fn remove_m1(&mut self, value: M1) {
-        if let Some(i) = self.m1.iter().position(|&v| v == value) {
-            self.m1.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m2(&mut self, value: M2) {
-        if let Some(i) = self.m2.iter().position(|&v| v == value) {
-            self.m2.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m3(&mut self, value: M3) {
-        if let Some(i) = self.m3.iter().position(|&v| v == value) {
-            self.m3.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m4(&mut self, value: M4) {
-        if let Some(i) = self.m4.iter().position(|&v| v == value) {
-            self.m4.swap_remove(i);
-        }
-    }
-
-    pub fn has_m1(&self, value: M1) -> bool {
-        self.m1.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m2(&self, value: M2) -> bool {
-        self.m2.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m3(&self, value: M3) -> bool {
-        self.m3.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m4(&self, value: M4) -> bool {
-        self.m4.iter().any(|&v| v == value)
-    }
-
-    pub fn base(&self) -> Marker::Raw {
-        self.base
-    }
-
-    pub fn get(&self) -> Marker::Raw {
-        (self.f)(self.base, &self.m1, &self.m2, &self.m3, &self.m4)
-    }
-
-    pub fn m1(&self) -> &SmallVec<[M1; N]> {
-        &self.m1
-    }
-
-    pub fn m2(&self) -> &SmallVec<[M2; N]> {
-        &self.m2
-    }
-
-    pub fn m3(&self) -> &SmallVec<[M3; N]> {
-        &self.m3
-    }
-
-    pub fn m4(&self) -> &SmallVec<[M4; N]> {
-        &self.m4
-    }
-}
-
-pub struct Stat5<Marker, M1, M2, M3, M4, M5, const N: usize = 2>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-    M5: Modifier,
-{
-    base: Marker::Raw,
-    m1: SmallVec<[M1; N]>,
-    m2: SmallVec<[M2; N]>,
-    m3: SmallVec<[M3; N]>,
-    m4: SmallVec<[M4; N]>,
-    m5: SmallVec<[M5; N]>,
-    f: Box<
-        dyn Fn(
-            Marker::Raw,
-            &SmallVec<[M1; N]>,
-            &SmallVec<[M2; N]>,
-            &SmallVec<[M3; N]>,
-            &SmallVec<[M4; N]>,
-            &SmallVec<[M5; N]>,
-        ) -> Marker::Raw + Send,
-    >,
-    _p: PhantomData<Marker>,
-}
-
-impl<Marker, M1, M2, M3, M4, M5, const N: usize> Stat5<Marker, M1, M2, M3, M4, M5, N>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-    M5: Modifier,
-{
-    pub fn new(
-        base: Marker::Raw,
-        f: Box<
-            dyn Fn(
-                Marker::Raw,
-                &SmallVec<[M1; N]>,
-                &SmallVec<[M2; N]>,
-                &SmallVec<[M3; N]>,
-                &SmallVec<[M4; N]>,
-                &SmallVec<[M5; N]>,
-            ) -> Marker::Raw + Send,
-        >,
-    ) -> Self {
-        Self {
-            base,
-            m1: SmallVec::new(),
-            m2: SmallVec::new(),
-            m3: SmallVec::new(),
-            m4: SmallVec::new(),
-            m5: SmallVec::new(),
-            f,
-            _p: PhantomData,
-        }
-    }
-
-    pub fn apply_m1(&mut self, value: M1) {
-        self.m1.push(value);
-    }
-
-    pub fn apply_m2(&mut self, value: M2) {
-        self.m2.push(value);
-    }
-
-    pub fn apply_m3(&mut self, value: M3) {
-        self.m3.push(value);
-    }
-
-    pub fn apply_m4(&mut self, value: M4) {
-        self.m4.push(value);
-    }
-
-    pub fn apply_m5(&mut self, value: M5) {
-        self.m5.push(value);
-    }
-
-    pub fn remove_m1(&mut self, value: M1) {
-        if let Some(i) = self.m1.iter().position(|&v| v == value) {
-            self.m1.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m2(&mut self, value: M2) {
-        if let Some(i) = self.m2.iter().position(|&v| v == value) {
-            self.m2.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m3(&mut self, value: M3) {
-        if let Some(i) = self.m3.iter().position(|&v| v == value) {
-            self.m3.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m4(&mut self, value: M4) {
-        if let Some(i) = self.m4.iter().position(|&v| v == value) {
-            self.m4.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m5(&mut self, value: M5) {
-        if let Some(i) = self.m5.iter().position(|&v| v == value) {
-            self.m5.swap_remove(i);
-        }
-    }
-
-    pub fn has_m1(&self, value: M1) -> bool {
-        self.m1.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m2(&self, value: M2) -> bool {
-        self.m2.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m3(&self, value: M3) -> bool {
-        self.m3.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m4(&self, value: M4) -> bool {
-        self.m4.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m5(&self, value: M5) -> bool {
-        self.m5.iter().any(|&v| v == value)
-    }
-
-    pub fn base(&self) -> Marker::Raw {
-        self.base
-    }
-
-    pub fn get(&self) -> Marker::Raw {
-        (self.f)(self.base, &self.m1, &self.m2, &self.m3, &self.m4, &self.m5)
-    }
-
-    pub fn m1(&self) -> &SmallVec<[M1; N]> {
-        &self.m1
-    }
-
-    pub fn m2(&self) -> &SmallVec<[M2; N]> {
-        &self.m2
-    }
-
-    pub fn m3(&self) -> &SmallVec<[M3; N]> {
-        &self.m3
-    }
-
-    pub fn m4(&self) -> &SmallVec<[M4; N]> {
-        &self.m4
-    }
-
-    pub fn m5(&self) -> &SmallVec<[M5; N]> {
-        &self.m5
-    }
-}
-
-pub struct Stat6<Marker, M1, M2, M3, M4, M5, M6, const N: usize = 2>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-    M5: Modifier,
-    M6: Modifier,
-{
-    base: Marker::Raw,
-    m1: SmallVec<[M1; N]>,
-    m2: SmallVec<[M2; N]>,
-    m3: SmallVec<[M3; N]>,
-    m4: SmallVec<[M4; N]>,
-    m5: SmallVec<[M5; N]>,
-    m6: SmallVec<[M6; N]>,
-    f: Box<
-        dyn Fn(
-            Marker::Raw,
-            &SmallVec<[M1; N]>,
-            &SmallVec<[M2; N]>,
-            &SmallVec<[M3; N]>,
-            &SmallVec<[M4; N]>,
-            &SmallVec<[M5; N]>,
-            &SmallVec<[M6; N]>,
-        ) -> Marker::Raw + Send,
-    >,
-    _p: PhantomData<Marker>,
-}
-
-impl<Marker, M1, M2, M3, M4, M5, M6, const N: usize> Stat6<Marker, M1, M2, M3, M4, M5, M6, N>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-    M5: Modifier,
-    M6: Modifier,
-{
-    pub fn new(
-        base: Marker::Raw,
-        f: Box<
-            dyn Fn(
-                Marker::Raw,
-                &SmallVec<[M1; N]>,
-                &SmallVec<[M2; N]>,
-                &SmallVec<[M3; N]>,
-                &SmallVec<[M4; N]>,
-                &SmallVec<[M5; N]>,
-                &SmallVec<[M6; N]>,
-            ) -> Marker::Raw + Send,
-        >,
-    ) -> Self {
-        Self {
-            base,
-            m1: SmallVec::new(),
-            m2: SmallVec::new(),
-            m3: SmallVec::new(),
-            m4: SmallVec::new(),
-            m5: SmallVec::new(),
-            m6: SmallVec::new(),
-            f,
-            _p: PhantomData,
-        }
-    }
-
-    pub fn apply_m1(&mut self, value: M1) {
-        self.m1.push(value);
-    }
-
-    pub fn apply_m2(&mut self, value: M2) {
-        self.m2.push(value);
-    }
-
-    pub fn apply_m3(&mut self, value: M3) {
-        self.m3.push(value);
-    }
-
-    pub fn apply_m4(&mut self, value: M4) {
-        self.m4.push(value);
-    }
-
-    pub fn apply_m5(&mut self, value: M5) {
-        self.m5.push(value);
-    }
-
-    pub fn apply_m6(&mut self, value: M6) {
-        self.m6.push(value);
-    }
-
-    pub fn remove_m1(&mut self, value: M1) {
-        if let Some(i) = self.m1.iter().position(|&v| v == value) {
-            self.m1.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m2(&mut self, value: M2) {
-        if let Some(i) = self.m2.iter().position(|&v| v == value) {
-            self.m2.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m3(&mut self, value: M3) {
-        if let Some(i) = self.m3.iter().position(|&v| v == value) {
-            self.m3.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m4(&mut self, value: M4) {
-        if let Some(i) = self.m4.iter().position(|&v| v == value) {
-            self.m4.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m5(&mut self, value: M5) {
-        if let Some(i) = self.m5.iter().position(|&v| v == value) {
-            self.m5.swap_remove(i);
-        }
-    }
-
-    pub fn remove_m6(&mut self, value: M6) {
-        if let Some(i) = self.m6.iter().position(|&v| v == value) {
-            self.m6.swap_remove(i);
-        }
-    }
-
-    pub fn has_m1(&self, value: M1) -> bool {
-        self.m1.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m2(&self, value: M2) -> bool {
-        self.m2.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m3(&self, value: M3) -> bool {
-        self.m3.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m4(&self, value: M4) -> bool {
-        self.m4.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m5(&self, value: M5) -> bool {
-        self.m5.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m6(&self, value: M6) -> bool {
-        self.m6.iter().any(|&v| v == value)
-    }
-
-    pub fn base(&self) -> Marker::Raw {
-        self.base
-    }
-
-    pub fn get(&self) -> Marker::Raw {
-        (self.f)(
-            self.base, &self.m1, &self.m2, &self.m3, &self.m4, &self.m5, &self.m6,
-        )
-    }
-
-    pub fn m1(&self) -> &SmallVec<[M1; N]> {
-        &self.m1
-    }
+use crate::{
+    error::Error,
+    formula::{Formula, FormulaRegistry, Formulated, StatFormula},
+    layer::{prefixed, FiniteLayers, HCons, Layer, LayerList, Selector},
+    layers,
+    modifier::Modifier,
+    raw::{Finite, Summation},
+};
 
-    pub fn m2(&self) -> &SmallVec<[M2; N]> {
-        &self.m2
-    }
+pub trait StatMarker: PartialEq + Clone + Copy {
+    type Raw: PartialEq + Clone + Copy + Add<Output = Self::Raw> + Mul<Output = Self::Raw>;
 
-    pub fn m3(&self) -> &SmallVec<[M3; N]> {
-        &self.m3
-    }
+    const SUMMATION: Summation = Summation::Naive;
 
-    pub fn m4(&self) -> &SmallVec<[M4; N]> {
-        &self.m4
+    /// Name of the marker type without its module path, as the derive produces it.
+    fn name() -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let path = name.split('<').next().unwrap_or(name);
+        path.rfind("::").map_or(path, |i| &path[i + 2..])
     }
 
-    pub fn m5(&self) -> &SmallVec<[M5; N]> {
-        &self.m5
+    /// Base value of stats created with `Default`, when the raw type's own default does not
+    /// fit.
+    fn default_base() -> Option<Self::Raw> {
+        None
     }
 
-    pub fn m6(&self) -> &SmallVec<[M6; N]> {
-        &self.m6
+    /// Applied to the final value of every stat of this kind, e.g. to keep it within bounds.
+    fn clamp(value: Self::Raw) -> Self::Raw {
+        value
     }
 }
 
-pub struct Stat7<Marker, M1, M2, M3, M4, M5, M6, M7, const N: usize = 2>
+#[cfg(feature = "derive")]
+pub use maxstat_derive::StatMarker;
+
+/// Stat with a base value and any number of modifier layers, combined by the formula `F`.
+///
+/// `Layers` is a list of layers written with [`layers!`](crate::layers). `Stat1` to
+/// `Stat8` name the common shapes and keep positional accessors such as `apply_m1`.
+pub struct Stat<Marker, Layers, F>
 where
     Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-    M5: Modifier,
-    M6: Modifier,
-    M7: Modifier,
 {
     base: Marker::Raw,
-    m1: SmallVec<[M1; N]>,
-    m2: SmallVec<[M2; N]>,
-    m3: SmallVec<[M3; N]>,
-    m4: SmallVec<[M4; N]>,
-    m5: SmallVec<[M5; N]>,
-    m6: SmallVec<[M6; N]>,
-    m7: SmallVec<[M7; N]>,
-    f: Box<
-        dyn Fn(
-            Marker::Raw,
+    layers: Layers,
+    f: F,
+    _p: PhantomData<Marker>,
+}
+
+pub type Stat1<Marker, M1, const N: usize = 2, F = DynFormula1<Marker, M1, N>> =
+    Stat<Marker, layers![M1; N], F>;
+
+pub type Stat2<Marker, M1, M2, const N: usize = 2, F = DynFormula2<Marker, M1, M2, N>> =
+    Stat<Marker, layers![M1, M2; N], F>;
+
+pub type Stat3<Marker, M1, M2, M3, const N: usize = 2, F = DynFormula3<Marker, M1, M2, M3, N>> =
+    Stat<Marker, layers![M1, M2, M3; N], F>;
+
+pub type Stat4<
+    Marker,
+    M1,
+    M2,
+    M3,
+    M4,
+    const N: usize = 2,
+    F = DynFormula4<Marker, M1, M2, M3, M4, N>,
+> = Stat<Marker, layers![M1, M2, M3, M4; N], F>;
+
+pub type Stat5<
+    Marker,
+    M1,
+    M2,
+    M3,
+    M4,
+    M5,
+    const N: usize = 2,
+    F = DynFormula5<Marker, M1, M2, M3, M4, M5, N>,
+> = Stat<Marker, layers![M1, M2, M3, M4, M5; N], F>;
+
+pub type Stat6<
+    Marker,
+    M1,
+    M2,
+    M3,
+    M4,
+    M5,
+    M6,
+    const N: usize = 2,
+    F = DynFormula6<Marker, M1, M2, M3, M4, M5, M6, N>,
+> = Stat<Marker, layers![M1, M2, M3, M4, M5, M6; N], F>;
+
+pub type Stat7<
+    Marker,
+    M1,
+    M2,
+    M3,
+    M4,
+    M5,
+    M6,
+    M7,
+    const N: usize = 2,
+    F = DynFormula7<Marker, M1, M2, M3, M4, M5, M6, M7, N>,
+> = Stat<Marker, layers![M1, M2, M3, M4, M5, M6, M7; N], F>;
+
+pub type Stat8<
+    Marker,
+    M1,
+    M2,
+    M3,
+    M4,
+    M5,
+    M6,
+    M7,
+    M8,
+    const N: usize = 2,
+    F = DynFormula8<Marker, M1, M2, M3, M4, M5, M6, M7, M8, N>,
+> = Stat<Marker, layers![M1, M2, M3, M4, M5, M6, M7, M8; N], F>;
+
+pub type DynFormula1<Marker, M1, const N: usize = 2> = Formula<
+    dyn Fn(<Marker as StatMarker>::Raw, &SmallVec<[M1; N]>) -> <Marker as StatMarker>::Raw
+        + Send
+        + Sync,
+>;
+
+pub type DynFormula2<Marker, M1, M2, const N: usize = 2> = Formula<
+    dyn Fn(
+            <Marker as StatMarker>::Raw,
+            &SmallVec<[M1; N]>,
+            &SmallVec<[M2; N]>,
+        ) -> <Marker as StatMarker>::Raw
+        + Send
+        + Sync,
+>;
+
+pub type DynFormula3<Marker, M1, M2, M3, const N: usize = 2> = Formula<
+    dyn Fn(
+            <Marker as StatMarker>::Raw,
+            &SmallVec<[M1; N]>,
+            &SmallVec<[M2; N]>,
+            &SmallVec<[M3; N]>,
+        ) -> <Marker as StatMarker>::Raw
+        + Send
+        + Sync,
+>;
+
+pub type DynFormula4<Marker, M1, M2, M3, M4, const N: usize = 2> = Formula<
+    dyn Fn(
+            <Marker as StatMarker>::Raw,
+            &SmallVec<[M1; N]>,
+            &SmallVec<[M2; N]>,
+            &SmallVec<[M3; N]>,
+            &SmallVec<[M4; N]>,
+        ) -> <Marker as StatMarker>::Raw
+        + Send
+        + Sync,
+>;
+
+pub type DynFormula5<Marker, M1, M2, M3, M4, M5, const N: usize = 2> = Formula<
+    dyn Fn(
+            <Marker as StatMarker>::Raw,
+            &SmallVec<[M1; N]>,
+            &SmallVec<[M2; N]>,
+            &SmallVec<[M3; N]>,
+            &SmallVec<[M4; N]>,
+            &SmallVec<[M5; N]>,
+        ) -> <Marker as StatMarker>::Raw
+        + Send
+        + Sync,
+>;
+
+pub type DynFormula6<Marker, M1, M2, M3, M4, M5, M6, const N: usize = 2> = Formula<
+    dyn Fn(
+            <Marker as StatMarker>::Raw,
+            &SmallVec<[M1; N]>,
+            &SmallVec<[M2; N]>,
+            &SmallVec<[M3; N]>,
+            &SmallVec<[M4; N]>,
+            &SmallVec<[M5; N]>,
+            &SmallVec<[M6; N]>,
+        ) -> <Marker as StatMarker>::Raw
+        + Send
+        + Sync,
+>;
+
+pub type DynFormula7<Marker, M1, M2, M3, M4, M5, M6, M7, const N: usize = 2> = Formula<
+    dyn Fn(
+            <Marker as StatMarker>::Raw,
             &SmallVec<[M1; N]>,
             &SmallVec<[M2; N]>,
             &SmallVec<[M3; N]>,
@@ -773,432 +203,421 @@ where
             &SmallVec<[M5; N]>,
             &SmallVec<[M6; N]>,
             &SmallVec<[M7; N]>,
-        ) -> Marker::Raw + Send,
-    >,
-    _p: PhantomData<Marker>,
-}
+        ) -> <Marker as StatMarker>::Raw
+        + Send
+        + Sync,
+>;
+
+pub type DynFormula8<Marker, M1, M2, M3, M4, M5, M6, M7, M8, const N: usize = 2> = Formula<
+    dyn Fn(
+            <Marker as StatMarker>::Raw,
+            &SmallVec<[M1; N]>,
+            &SmallVec<[M2; N]>,
+            &SmallVec<[M3; N]>,
+            &SmallVec<[M4; N]>,
+            &SmallVec<[M5; N]>,
+            &SmallVec<[M6; N]>,
+            &SmallVec<[M7; N]>,
+            &SmallVec<[M8; N]>,
+        ) -> <Marker as StatMarker>::Raw
+        + Send
+        + Sync,
+>;
 
-impl<Marker, M1, M2, M3, M4, M5, M6, M7, const N: usize>
-    Stat7<Marker, M1, M2, M3, M4, M5, M6, M7, N>
+impl<Marker, Layers, G> Stat<Marker, Layers, Formula<G>>
 where
     Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-    M5: Modifier,
-    M6: Modifier,
-    M7: Modifier,
+    Layers: LayerList,
+    G: ?Sized,
+    Formula<G>: StatFormula<Marker::Raw, Layers>,
 {
-    pub fn new(
-        base: Marker::Raw,
-        f: Box<
-            dyn Fn(
-                Marker::Raw,
-                &SmallVec<[M1; N]>,
-                &SmallVec<[M2; N]>,
-                &SmallVec<[M3; N]>,
-                &SmallVec<[M4; N]>,
-                &SmallVec<[M5; N]>,
-                &SmallVec<[M6; N]>,
-                &SmallVec<[M7; N]>,
-            ) -> Marker::Raw + Send,
-        >,
-    ) -> Self {
-        Self {
-            base,
-            m1: SmallVec::new(),
-            m2: SmallVec::new(),
-            m3: SmallVec::new(),
-            m4: SmallVec::new(),
-            m5: SmallVec::new(),
-            m6: SmallVec::new(),
-            m7: SmallVec::new(),
-            f,
-            _p: PhantomData,
-        }
-    }
-
-    pub fn apply_m1(&mut self, value: M1) {
-        self.m1.push(value);
-    }
-
-    pub fn apply_m2(&mut self, value: M2) {
-        self.m2.push(value);
-    }
-
-    pub fn apply_m3(&mut self, value: M3) {
-        self.m3.push(value);
-    }
-
-    pub fn apply_m4(&mut self, value: M4) {
-        self.m4.push(value);
+    pub fn new(base: Marker::Raw, f: Box<G>) -> Self {
+        Self::from_formula(base, Formula::anonymous(Arc::from(f)))
     }
 
-    pub fn apply_m5(&mut self, value: M5) {
-        self.m5.push(value);
+    pub fn from_registry(base: Marker::Raw, registry: &FormulaRegistry, name: &str) -> Option<Self>
+    where
+        Self: Formulated<Formula = G>,
+    {
+        registry
+            .get::<Self>(name)
+            .map(|f| Self::from_formula(base, f))
     }
 
-    pub fn apply_m6(&mut self, value: M6) {
-        self.m6.push(value);
+    pub fn formula_name(&self) -> Option<&str> {
+        self.f.name()
     }
 
-    pub fn apply_m7(&mut self, value: M7) {
-        self.m7.push(value);
+    pub fn set_formula_name(&mut self, name: impl Into<Arc<str>>) {
+        self.f = self.f.renamed(name);
     }
+}
 
-    pub fn remove_m1(&mut self, value: M1) {
-        if let Some(i) = self.m1.iter().position(|&v| v == value) {
-            self.m1.swap_remove(i);
-        }
+impl<Marker, Layers, F> Stat<Marker, Layers, F>
+where
+    Marker: StatMarker,
+    Layers: LayerList,
+    F: StatFormula<Marker::Raw, Layers>,
+{
+    pub fn from_formula(base: Marker::Raw, f: F) -> Self {
+        Self::from_parts(base, Layers::default(), f)
     }
 
-    pub fn remove_m2(&mut self, value: M2) {
-        if let Some(i) = self.m2.iter().position(|&v| v == value) {
-            self.m2.swap_remove(i);
+    pub(crate) fn from_parts(base: Marker::Raw, layers: Layers, f: F) -> Self {
+        Self {
+            base,
+            layers,
+            f,
+            _p: PhantomData,
         }
     }
 
-    pub fn remove_m3(&mut self, value: M3) {
-        if let Some(i) = self.m3.iter().position(|&v| v == value) {
-            self.m3.swap_remove(i);
-        }
+    /// Adds `value` to the layer of its modifier type.
+    pub fn apply<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        self.layers.select_mut().push(value);
     }
 
-    pub fn remove_m4(&mut self, value: M4) {
-        if let Some(i) = self.m4.iter().position(|&v| v == value) {
-            self.m4.swap_remove(i);
+    pub fn try_apply<M, I>(&mut self, value: M) -> Result<(), Error>
+    where
+        M: Modifier,
+        <M::Target as StatMarker>::Raw: Finite,
+        Layers: Selector<M, I>,
+    {
+        let layer = <Layers as Selector<M, I>>::INDEX + 1;
+        if !value.raw().is_finite() {
+            return Err(Error::InvalidValue { layer });
         }
+        let modifiers = self.layers.select_mut();
+        modifiers
+            .try_reserve(1)
+            .map_err(|_| Error::Capacity { layer })?;
+        modifiers.push(value);
+        Ok(())
     }
 
-    pub fn remove_m5(&mut self, value: M5) {
-        if let Some(i) = self.m5.iter().position(|&v| v == value) {
-            self.m5.swap_remove(i);
+    pub fn remove<M, I>(&mut self, value: M)
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        let layer = self.layers.select_mut();
+        if let Some(i) = layer.iter().position(|&v| v == value) {
+            layer.swap_remove(i);
         }
     }
 
-    pub fn remove_m6(&mut self, value: M6) {
-        if let Some(i) = self.m6.iter().position(|&v| v == value) {
-            self.m6.swap_remove(i);
-        }
+    /// Like [`remove`](Self::remove), but fails if the layer does not contain `value`.
+    pub fn try_remove<M, I>(&mut self, value: M) -> Result<(), Error>
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        let layer = self.layers.select_mut();
+        let i = layer
+            .iter()
+            .position(|&v| v == value)
+            .ok_or(Error::NotFound {
+                layer: <Layers as Selector<M, I>>::INDEX + 1,
+            })?;
+        layer.swap_remove(i);
+        Ok(())
     }
 
-    pub fn remove_m7(&mut self, value: M7) {
-        if let Some(i) = self.m7.iter().position(|&v| v == value) {
-            self.m7.swap_remove(i);
-        }
+    pub fn has<M, I>(&self, value: M) -> bool
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        self.layers.select().contains(&value)
     }
 
-    pub fn has_m1(&self, value: M1) -> bool {
-        self.m1.iter().any(|&v| v == value)
+    /// Keeps only the modifiers of type `M` for which `f` returns `true`.
+    pub fn retain<M, I>(&mut self, f: impl FnMut(&mut M) -> bool)
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        self.layers.select_mut().retain(f);
     }
 
-    pub fn has_m2(&self, value: M2) -> bool {
-        self.m2.iter().any(|&v| v == value)
+    pub fn clear(&mut self) {
+        self.layers = Layers::default();
     }
 
-    pub fn has_m3(&self, value: M3) -> bool {
-        self.m3.iter().any(|&v| v == value)
+    pub fn layer<M, I>(&self) -> &SmallVec<<Layers as Selector<M, I>>::Array>
+    where
+        M: Modifier,
+        Layers: Selector<M, I>,
+    {
+        self.layers.select()
     }
 
-    pub fn has_m4(&self, value: M4) -> bool {
-        self.m4.iter().any(|&v| v == value)
+    pub fn layers(&self) -> &Layers {
+        &self.layers
     }
 
-    pub fn has_m5(&self, value: M5) -> bool {
-        self.m5.iter().any(|&v| v == value)
+    pub fn base(&self) -> Marker::Raw {
+        self.base
     }
 
-    pub fn has_m6(&self, value: M6) -> bool {
-        self.m6.iter().any(|&v| v == value)
+    pub fn set_base(&mut self, base: Marker::Raw) {
+        self.base = base;
     }
 
-    pub fn has_m7(&self, value: M7) -> bool {
-        self.m7.iter().any(|&v| v == value)
+    pub fn formula(&self) -> &F {
+        &self.f
     }
 
-    pub fn base(&self) -> Marker::Raw {
-        self.base
+    /// Replaces the formula, keeping the applied modifiers.
+    pub fn set_formula(&mut self, f: F) {
+        self.f = f;
     }
 
     pub fn get(&self) -> Marker::Raw {
-        (self.f)(
-            self.base, &self.m1, &self.m2, &self.m3, &self.m4, &self.m5, &self.m6, &self.m7,
-        )
-    }
-
-    pub fn m1(&self) -> &SmallVec<[M1; N]> {
-        &self.m1
+        Marker::clamp(self.f.eval(self.base, &self.layers))
     }
 
-    pub fn m2(&self) -> &SmallVec<[M2; N]> {
-        &self.m2
+    pub fn try_get(&self) -> Result<Marker::Raw, Error>
+    where
+        Marker::Raw: Finite,
+        Layers: FiniteLayers,
+    {
+        let value = self.get();
+        if value.is_finite() {
+            return Ok(value);
+        }
+        if !self.base.is_finite() {
+            return Err(Error::NonFinite { layer: Some(0) });
+        }
+        if let Some(i) = self.layers.non_finite() {
+            return Err(Error::NonFinite { layer: Some(i + 1) });
+        }
+        Err(Error::NonFinite { layer: None })
     }
+}
 
-    pub fn m3(&self) -> &SmallVec<[M3; N]> {
-        &self.m3
-    }
+macro_rules! impl_layer_access {
+    ($layer:literal, [$($h:ident),*], $m:ident, [$($tail:ident)*], $get:ident, $apply:ident, $try_apply:ident, $remove:ident, $try_remove:ident, $has:ident) => {
+        impl<Marker, $($h,)* $m, T, const N: usize, F> Stat<Marker, prefixed!([$($h),*], HCons<Layer<$m, N>, T>), F>
+        where
+            Marker: StatMarker,
+            $m: Modifier,
+        {
+            pub fn $apply(&mut self, value: $m) {
+                self.layers$(.$tail)*.head.push(value);
+            }
 
-    pub fn m4(&self) -> &SmallVec<[M4; N]> {
-        &self.m4
-    }
+            pub fn $try_apply(&mut self, value: $m) -> Result<(), Error>
+            where
+                <$m::Target as StatMarker>::Raw: Finite,
+            {
+                if !value.raw().is_finite() {
+                    return Err(Error::InvalidValue { layer: $layer });
+                }
+                let layer = &mut self.layers$(.$tail)*.head;
+                layer.try_reserve(1).map_err(|_| Error::Capacity { layer: $layer })?;
+                layer.push(value);
+                Ok(())
+            }
 
-    pub fn m5(&self) -> &SmallVec<[M5; N]> {
-        &self.m5
-    }
+            pub fn $remove(&mut self, value: $m) {
+                let layer = &mut self.layers$(.$tail)*.head;
+                if let Some(i) = layer.iter().position(|&v| v == value) {
+                    layer.swap_remove(i);
+                }
+            }
 
-    pub fn m6(&self) -> &SmallVec<[M6; N]> {
-        &self.m6
-    }
+            pub fn $try_remove(&mut self, value: $m) -> Result<(), Error> {
+                let layer = &mut self.layers$(.$tail)*.head;
+                let i = layer
+                    .iter()
+                    .position(|&v| v == value)
+                    .ok_or(Error::NotFound { layer: $layer })?;
+                layer.swap_remove(i);
+                Ok(())
+            }
 
-    pub fn m7(&self) -> &SmallVec<[M7; N]> {
-        &self.m7
-    }
-}
+            pub fn $has(&self, value: $m) -> bool {
+                self.layers$(.$tail)*.head.contains(&value)
+            }
 
-pub struct Stat8<Marker, M1, M2, M3, M4, M5, M6, M7, M8, const N: usize = 2>
-where
-    Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-    M5: Modifier,
-    M6: Modifier,
-    M7: Modifier,
-    M8: Modifier,
-{
-    base: Marker::Raw,
-    m1: SmallVec<[M1; N]>,
-    m2: SmallVec<[M2; N]>,
-    m3: SmallVec<[M3; N]>,
-    m4: SmallVec<[M4; N]>,
-    m5: SmallVec<[M5; N]>,
-    m6: SmallVec<[M6; N]>,
-    m7: SmallVec<[M7; N]>,
-    m8: SmallVec<[M8; N]>,
-    f: Box<
-        dyn Fn(
-            Marker::Raw,
-            &SmallVec<[M1; N]>,
-            &SmallVec<[M2; N]>,
-            &SmallVec<[M3; N]>,
-            &SmallVec<[M4; N]>,
-            &SmallVec<[M5; N]>,
-            &SmallVec<[M6; N]>,
-            &SmallVec<[M7; N]>,
-            &SmallVec<[M8; N]>,
-        ) -> Marker::Raw + Send,
-    >,
-    _p: PhantomData<Marker>,
+            pub fn $get(&self) -> &Layer<$m, N> {
+                &self.layers$(.$tail)*.head
+            }
+        }
+    };
 }
 
-impl<Marker, M1, M2, M3, M4, M5, M6, M7, M8, const N: usize>
-    Stat8<Marker, M1, M2, M3, M4, M5, M6, M7, M8, N>
+impl_layer_access!(
+    1,
+    [],
+    M1,
+    [],
+    m1,
+    apply_m1,
+    try_apply_m1,
+    remove_m1,
+    try_remove_m1,
+    has_m1
+);
+impl_layer_access!(
+    2,
+    [H1],
+    M2,
+    [tail],
+    m2,
+    apply_m2,
+    try_apply_m2,
+    remove_m2,
+    try_remove_m2,
+    has_m2
+);
+impl_layer_access!(3, [H1, H2], M3, [tail tail], m3, apply_m3, try_apply_m3, remove_m3, try_remove_m3, has_m3);
+impl_layer_access!(4, [H1, H2, H3], M4, [tail tail tail], m4, apply_m4, try_apply_m4, remove_m4, try_remove_m4, has_m4);
+impl_layer_access!(5, [H1, H2, H3, H4], M5, [tail tail tail tail], m5, apply_m5, try_apply_m5, remove_m5, try_remove_m5, has_m5);
+impl_layer_access!(6, [H1, H2, H3, H4, H5], M6, [tail tail tail tail tail], m6, apply_m6, try_apply_m6, remove_m6, try_remove_m6, has_m6);
+impl_layer_access!(7, [H1, H2, H3, H4, H5, H6], M7, [tail tail tail tail tail tail], m7, apply_m7, try_apply_m7, remove_m7, try_remove_m7, has_m7);
+impl_layer_access!(8, [H1, H2, H3, H4, H5, H6, H7], M8, [tail tail tail tail tail tail tail], m8, apply_m8, try_apply_m8, remove_m8, try_remove_m8, has_m8);
+
+impl<Marker, Layers, F> Clone for Stat<Marker, Layers, F>
 where
     Marker: StatMarker,
-    M1: Modifier,
-    M2: Modifier,
-    M3: Modifier,
-    M4: Modifier,
-    M5: Modifier,
-    M6: Modifier,
-    M7: Modifier,
-    M8: Modifier,
+    Layers: Clone,
+    F: Clone,
 {
-    pub fn new(
-        base: Marker::Raw,
-        f: Box<
-            dyn Fn(
-                Marker::Raw,
-                &SmallVec<[M1; N]>,
-                &SmallVec<[M2; N]>,
-                &SmallVec<[M3; N]>,
-                &SmallVec<[M4; N]>,
-                &SmallVec<[M5; N]>,
-                &SmallVec<[M6; N]>,
-                &SmallVec<[M7; N]>,
-                &SmallVec<[M8; N]>,
-            ) -> Marker::Raw + Send,
-        >,
-    ) -> Self {
+    fn clone(&self) -> Self {
         Self {
-            base,
-            m1: SmallVec::new(),
-            m2: SmallVec::new(),
-            m3: SmallVec::new(),
-            m4: SmallVec::new(),
-            m5: SmallVec::new(),
-            m6: SmallVec::new(),
-            m7: SmallVec::new(),
-            m8: SmallVec::new(),
-            f,
+            base: self.base,
+            layers: self.layers.clone(),
+            f: self.f.clone(),
             _p: PhantomData,
         }
     }
+}
 
-    pub fn apply_m1(&mut self, value: M1) {
-        self.m1.push(value);
-    }
-
-    pub fn apply_m2(&mut self, value: M2) {
-        self.m2.push(value);
-    }
-
-    pub fn apply_m3(&mut self, value: M3) {
-        self.m3.push(value);
-    }
-
-    pub fn apply_m4(&mut self, value: M4) {
-        self.m4.push(value);
-    }
-
-    pub fn apply_m5(&mut self, value: M5) {
-        self.m5.push(value);
-    }
-
-    pub fn apply_m6(&mut self, value: M6) {
-        self.m6.push(value);
-    }
-
-    pub fn apply_m7(&mut self, value: M7) {
-        self.m7.push(value);
+impl<Marker, Layers, F> fmt::Debug for Stat<Marker, Layers, F>
+where
+    Marker: StatMarker,
+    Marker::Raw: fmt::Debug,
+    Layers: LayerList + fmt::Debug,
+    F: StatFormula<Marker::Raw, Layers>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stat")
+            .field("base", &self.base)
+            .field("layers", &self.layers)
+            .field("value", &self.get())
+            .finish()
     }
+}
 
-    pub fn apply_m8(&mut self, value: M8) {
-        self.m8.push(value);
-    }
+impl<Marker, Layers, G> Formulated for Stat<Marker, Layers, Formula<G>>
+where
+    Marker: StatMarker,
+    G: ?Sized + Send + Sync + 'static,
+{
+    type Formula = G;
+}
 
-    pub fn remove_m1(&mut self, value: M1) {
-        if let Some(i) = self.m1.iter().position(|&v| v == value) {
-            self.m1.swap_remove(i);
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    pub fn remove_m2(&mut self, value: M2) {
-        if let Some(i) = self.m2.iter().position(|&v| v == value) {
-            self.m2.swap_remove(i);
-        }
-    }
+    #[test]
+    fn test() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
 
-    pub fn remove_m3(&mut self, value: M3) {
-        if let Some(i) = self.m3.iter().position(|&v| v == value) {
-            self.m3.swap_remove(i);
+        impl StatMarker for DummyMarker {
+            type Raw = f32;
         }
-    }
 
-    pub fn remove_m4(&mut self, value: M4) {
-        if let Some(i) = self.m4.iter().position(|&v| v == value) {
-            self.m4.swap_remove(i);
+        #[derive(Clone, Copy, PartialEq)]
+        struct DummyModifier {
+            raw: f32,
         }
-    }
 
-    pub fn remove_m5(&mut self, value: M5) {
-        if let Some(i) = self.m5.iter().position(|&v| v == value) {
-            self.m5.swap_remove(i);
-        }
-    }
+        impl Modifier for DummyModifier {
+            type Target = DummyMarker;
 
-    pub fn remove_m6(&mut self, value: M6) {
-        if let Some(i) = self.m6.iter().position(|&v| v == value) {
-            self.m6.swap_remove(i);
-        }
-    }
+            fn from_raw(raw: <<Self as Modifier>::Target as StatMarker>::Raw) -> Self {
+                Self { raw }
+            }
 
-    pub fn remove_m7(&mut self, value: M7) {
-        if let Some(i) = self.m7.iter().position(|&v| v == value) {
-            self.m7.swap_remove(i);
-        }
-    }
+            fn raw(&self) -> <<Self as Modifier>::Target as StatMarker>::Raw {
+                self.raw
+            }
 
-    pub fn remove_m8(&mut self, value: M8) {
-        if let Some(i) = self.m8.iter().position(|&v| v == value) {
-            self.m8.swap_remove(i);
+            fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
+                s.iter().fold(0., |acc, m| acc + m.raw)
+            }
         }
-    }
-
-    pub fn has_m1(&self, value: M1) -> bool {
-        self.m1.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m2(&self, value: M2) -> bool {
-        self.m2.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m3(&self, value: M3) -> bool {
-        self.m3.iter().any(|&v| v == value)
-    }
-
-    pub fn has_m4(&self, value: M4) -> bool {
-        self.m4.iter().any(|&v| v == value)
-    }
 
-    pub fn has_m5(&self, value: M5) -> bool {
-        self.m5.iter().any(|&v| v == value)
-    }
+        let mut stat = Stat1::<DummyMarker, DummyModifier>::new(
+            1.,
+            Box::new(|b, v| b + DummyModifier::combine(v)),
+        );
+        stat.apply_m1(DummyModifier::from_raw(1.));
+        stat.apply_m1(DummyModifier::from_raw(2.));
+        stat.apply_m1(DummyModifier::from_raw(3.));
 
-    pub fn has_m6(&self, value: M6) -> bool {
-        self.m6.iter().any(|&v| v == value)
+        assert_eq!(7., stat.get());
     }
 
-    pub fn has_m7(&self, value: M7) -> bool {
-        self.m7.iter().any(|&v| v == value)
-    }
+    #[test]
+    fn test_registry() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
 
-    pub fn has_m8(&self, value: M8) -> bool {
-        self.m8.iter().any(|&v| v == value)
-    }
+        impl StatMarker for DummyMarker {
+            type Raw = f32;
+        }
 
-    pub fn base(&self) -> Marker::Raw {
-        self.base
-    }
+        type Sum = crate::modifier::standard::Flat<DummyMarker, f32, ()>;
 
-    pub fn get(&self) -> Marker::Raw {
-        (self.f)(
-            self.base, &self.m1, &self.m2, &self.m3, &self.m4, &self.m5, &self.m6, &self.m7,
-            &self.m8,
-        )
-    }
+        let mut registry = FormulaRegistry::new();
+        registry.register::<Stat1<DummyMarker, Sum>>("sum", Box::new(|b, v| b + Sum::combine(v)));
 
-    pub fn m1(&self) -> &SmallVec<[M1; N]> {
-        &self.m1
-    }
+        let mut a = Stat1::<DummyMarker, Sum>::from_registry(1., &registry, "sum").unwrap();
+        a.apply_m1(Sum::from_raw(2.));
 
-    pub fn m2(&self) -> &SmallVec<[M2; N]> {
-        &self.m2
-    }
+        let mut b = a.clone();
+        b.apply_m1(Sum::from_raw(3.));
 
-    pub fn m3(&self) -> &SmallVec<[M3; N]> {
-        &self.m3
+        assert!(a.formula().ptr_eq(b.formula()));
+        assert_eq!(Some("sum"), b.formula_name());
+        assert_eq!(3., a.get());
+        assert_eq!(6., b.get());
+        assert!(Stat1::<DummyMarker, Sum>::from_registry(1., &registry, "product").is_none());
     }
 
-    pub fn m4(&self) -> &SmallVec<[M4; N]> {
-        &self.m4
-    }
+    #[test]
+    fn test_static_formula() {
+        #[derive(PartialEq, Clone, Copy)]
+        struct DummyMarker;
 
-    pub fn m5(&self) -> &SmallVec<[M5; N]> {
-        &self.m5
-    }
+        impl StatMarker for DummyMarker {
+            type Raw = f32;
+        }
 
-    pub fn m6(&self) -> &SmallVec<[M6; N]> {
-        &self.m6
-    }
+        type Sum = crate::modifier::standard::Flat<DummyMarker, f32, ()>;
 
-    pub fn m7(&self) -> &SmallVec<[M7; N]> {
-        &self.m7
-    }
+        let mut stat =
+            Stat1::<DummyMarker, Sum, 2, _>::from_formula(1., |b: f32, v: &SmallVec<[Sum; 2]>| {
+                b + Sum::combine(v)
+            });
+        stat.apply_m1(Sum::from_raw(2.));
 
-    pub fn m8(&self) -> &SmallVec<[M8; N]> {
-        &self.m8
+        assert_eq!(3., stat.get());
+        assert_eq!(3., stat.clone().get());
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test() {
+    fn test_layers() {
         #[derive(PartialEq, Clone, Copy)]
         struct DummyMarker;
 
@@ -1206,35 +625,63 @@ mod tests {
             type Raw = f32;
         }
 
-        #[derive(Clone, Copy, PartialEq)]
-        struct DummyModifier {
-            raw: f32,
-        }
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        struct Tagged<const K: usize>(f32);
 
-        impl Modifier for DummyModifier {
+        impl<const K: usize> Modifier for Tagged<K> {
             type Target = DummyMarker;
 
-            fn from_raw(raw: <<Self as Modifier>::Target as StatMarker>::Raw) -> Self {
-                Self { raw }
+            fn from_raw(raw: f32) -> Self {
+                Self(raw)
             }
 
-            fn raw(&self) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-                self.raw
+            fn raw(&self) -> f32 {
+                self.0
             }
 
-            fn combine(s: &[Self]) -> <<Self as Modifier>::Target as StatMarker>::Raw {
-                s.iter().fold(0., |acc, m| acc + m.raw)
+            fn combine(s: &[Self]) -> f32 {
+                s.iter().fold(0., |acc, m| acc + m.0)
             }
         }
 
-        let mut stat = Stat1::<DummyMarker, DummyModifier>::new(
-            1.,
-            Box::new(|b, v| b + DummyModifier::combine(&v)),
+        type Nine = layers![
+            Tagged<1>,
+            Tagged<2>,
+            Tagged<3>,
+            Tagged<4>,
+            Tagged<5>,
+            Tagged<6>,
+            Tagged<7>,
+            Tagged<8>,
+            Tagged<9>
+        ];
+
+        struct Scaled;
+
+        impl StatFormula<f32, Nine> for Scaled {
+            fn eval(&self, base: f32, layers: &Nine) -> f32 {
+                (base + Tagged::<1>::combine(&layers.head))
+                    * Tagged::<9>::combine(Selector::<Tagged<9>, _>::select(layers))
+            }
+        }
+
+        let mut stat = Stat::<DummyMarker, Nine, _>::from_formula(1., Scaled);
+        stat.apply(Tagged::<1>(2.));
+        stat.apply(Tagged::<9>(2.));
+        stat.apply(Tagged::<9>(1.));
+        stat.apply_m1(Tagged::<1>(1.));
+
+        assert_eq!(12., stat.get());
+        assert_eq!(2, stat.layer::<Tagged<9>, _>().len());
+        assert!(stat.has(Tagged::<1>(2.)));
+        assert_eq!(
+            Err(Error::InvalidValue { layer: 9 }),
+            stat.try_apply(Tagged::<9>(f32::NAN))
         );
-        stat.apply_m1(DummyModifier::from_raw(1.));
-        stat.apply_m1(DummyModifier::from_raw(2.));
-        stat.apply_m1(DummyModifier::from_raw(3.));
 
-        assert_eq!(7., stat.get());
+        stat.remove(Tagged::<9>(1.));
+
+        assert_eq!(8., stat.get());
+        assert_eq!(2, stat.m1().len());
     }
 }
@@ -0,0 +1,249 @@
+use std::ops::DerefMut;
+
+use crate::{
+    cell::{standard::StandardStat, SharedCell, StatCell},
+    error::Error,
+    formula::StatFormula,
+    layer::LayerList,
+    modifier::standard::{StandardFormula, StandardLayers},
+    raw::StandardRaw,
+    stat::{Stat, StatMarker},
+};
+
+/// Shared stat that can take part in a transaction of a [`StatSet`].
+pub trait Transact {
+    type Stat: Clone;
+
+    type Guard<'a>: DerefMut<Target = Self::Stat>
+    where
+        Self: 'a;
+
+    fn try_lock(&self) -> Result<Self::Guard<'_>, Error>;
+}
+
+impl<Marker, Layers, F, P> Transact for StatCell<Marker, Layers, F, P>
+where
+    Marker: StatMarker,
+    Layers: LayerList + Clone,
+    F: StatFormula<Marker::Raw, Layers> + Clone,
+    P: SharedCell,
+{
+    type Stat = Stat<Marker, Layers, F>;
+
+    type Guard<'a>
+        = P::RefMut<'a, Stat<Marker, Layers, F>>
+    where
+        Self: 'a;
+
+    fn try_lock(&self) -> Result<Self::Guard<'_>, Error> {
+        P::try_write(&self.0)
+    }
+}
+
+impl<Marker, Metadata, P, const N: usize> Transact for StandardStat<Marker, Metadata, P, N>
+where
+    Marker: StatMarker,
+    Marker::Raw: StandardRaw,
+    Metadata: PartialEq + Clone + Copy,
+    StandardFormula: StatFormula<Marker::Raw, StandardLayers<Marker, Metadata, N>>,
+    P: SharedCell,
+{
+    type Stat = Stat<Marker, StandardLayers<Marker, Metadata, N>, StandardFormula>;
+
+    type Guard<'a>
+        = P::RefMut<'a, Self::Stat>
+    where
+        Self: 'a;
+
+    fn try_lock(&self) -> Result<Self::Guard<'_>, Error> {
+        P::try_write(&self.0)
+    }
+}
+
+/// Tuple of shared stats changed together, e.g. every stat touched by equipping an item.
+///
+/// [`transact`](Self::transact) locks the stats in order of address, so transactions over
+/// overlapping sets cannot deadlock, and other threads see either none or all of the changes.
+/// Every transaction clones the stats to restore them, layers and formula included.
+pub trait StatSet {
+    /// Write guards of the stats, in the order of the tuple.
+    type Guards;
+
+    /// Runs `f` on the locked stats, restoring all of them if it returns an error or panics.
+    ///
+    /// Fails without changing anything if a stat cannot be locked. Panics if the same stat
+    /// appears twice. A panic in `f` still poisons the `std` locks, but the stats keep
+    /// their previous modifiers.
+    fn transact<R, E>(self, f: impl FnOnce(&mut Self::Guards) -> Result<R, E>) -> Result<R, E>
+    where
+        E: From<Error>;
+}
+
+fn address<T>(stat: &T) -> usize {
+    stat as *const T as usize
+}
+
+/// Restores the snapshot of a transaction when dropped, unless it was taken back.
+struct Rollback<'g, G, S> {
+    guards: &'g mut G,
+    snapshot: Option<S>,
+    restore: fn(&mut G, S),
+}
+
+impl<G, S> Drop for Rollback<'_, G, S> {
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            (self.restore)(self.guards, snapshot);
+        }
+    }
+}
+
+macro_rules! impl_stat_set {
+    ($n:literal; $(($s:ident, $g:ident, $i:tt)),*) => {
+        impl<'s, $($s),*> StatSet for ($(&'s $s,)*)
+        where
+            $($s: Transact,)*
+        {
+            type Guards = ($($s::Guard<'s>,)*);
+
+            fn transact<R, E>(
+                self,
+                f: impl FnOnce(&mut Self::Guards) -> Result<R, E>,
+            ) -> Result<R, E>
+            where
+                E: From<Error>,
+            {
+                let addresses = [$(address(self.$i)),*];
+                let mut order: [usize; $n] = std::array::from_fn(|i| i);
+                order.sort_unstable_by_key(|&i| addresses[i]);
+                assert!(
+                    order.windows(2).all(|w| addresses[w[0]] != addresses[w[1]]),
+                    "stat appears twice in a transaction"
+                );
+
+                $(let mut $g = None;)*
+                for i in order {
+                    match i {
+                        $($i => $g = Some(self.$i.try_lock()?),)*
+                        _ => unreachable!(),
+                    }
+                }
+                let mut guards = ($($g.unwrap(),)*);
+
+                let snapshot = ($((*guards.$i).clone(),)*);
+                let mut rollback = Rollback {
+                    guards: &mut guards,
+                    snapshot: Some(snapshot),
+                    restore: |guards, snapshot| {
+                        $(*guards.$i = snapshot.$i;)*
+                    },
+                };
+                let result = f(rollback.guards);
+                if result.is_ok() {
+                    rollback.snapshot = None;
+                }
+                result
+            }
+        }
+    };
+}
+
+impl_stat_set!(1; (A, a, 0));
+impl_stat_set!(2; (A, a, 0), (B, b, 1));
+impl_stat_set!(3; (A, a, 0), (B, b, 1), (C, c, 2));
+impl_stat_set!(4; (A, a, 0), (B, b, 1), (C, c, 2), (D, d, 3));
+impl_stat_set!(5; (A, a, 0), (B, b, 1), (C, c, 2), (D, d, 3), (E1, e, 4));
+impl_stat_set!(6; (A, a, 0), (B, b, 1), (C, c, 2), (D, d, 3), (E1, e, 4), (F, f1, 5));
+impl_stat_set!(7; (A, a, 0), (B, b, 1), (C, c, 2), (D, d, 3), (E1, e, 4), (F, f1, 5), (G, g, 6));
+impl_stat_set!(8; (A, a, 0), (B, b, 1), (C, c, 2), (D, d, 3), (E1, e, 4), (F, f1, 5), (G, g, 6), (H, h, 7));
+
+#[cfg(test)]
+mod tests {
+    use std::panic::AssertUnwindSafe;
+
+    use super::*;
+    use crate::{
+        modifier::{standard::Flat, Modifier},
+        send::standard::{StandardStatCS, StandardStatS},
+    };
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct Strength;
+
+    impl StatMarker for Strength {
+        type Raw = f32;
+    }
+
+    #[derive(PartialEq, Clone, Copy)]
+    struct Armor;
+
+    impl StatMarker for Armor {
+        type Raw = f32;
+    }
+
+    #[test]
+    fn test_transact() {
+        let strength = StandardStatS::<Strength, ()>::new(10.);
+        let armor = StandardStatCS::<Armor, ()>::new(5.);
+
+        let result = (&strength, &armor).transact(|(strength, armor)| {
+            strength.apply_m1(Flat::from_raw(2.));
+            armor.try_apply_m1(Flat::from_raw(3.))
+        });
+
+        assert_eq!(Ok(()), result);
+        assert_eq!((12., 8.), (strength.get(), armor.get()));
+
+        let result = (&armor, &strength).transact(|(armor, strength)| {
+            strength.remove_m1(Flat::from_raw(2.));
+            armor.try_apply_m1(Flat::from_raw(f32::NAN))
+        });
+
+        assert_eq!(Err(Error::InvalidValue { layer: 1 }), result);
+        assert_eq!((12., 8.), (strength.get(), armor.get()));
+
+        for _ in 0..5 {
+            strength.apply_flat(Flat::from_raw(1.));
+        }
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                for _ in 0..1000 {
+                    let _ = (&strength, &armor).transact(|(strength, armor)| {
+                        strength.apply_m1(Flat::from_raw(1.));
+                        armor.try_remove_m1(Flat::from_raw(1.))
+                    });
+                }
+            });
+            for _ in 0..1000 {
+                let _ = (&armor, &strength).transact(|(armor, strength)| {
+                    armor.apply_m1(Flat::from_raw(1.));
+                    strength.try_remove_m1(Flat::from_raw(1.))
+                });
+            }
+        });
+
+        // Points only move when the other stat has one to give.
+        assert_eq!(25., strength.get() + armor.get());
+    }
+
+    #[test]
+    fn test_panic() {
+        let strength = StandardStatS::<Strength, ()>::new(10.);
+        let armor = StandardStatCS::<Armor, ()>::new(5.);
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            (&strength, &armor).transact(|(strength, armor)| -> Result<(), Error> {
+                strength.apply_m1(Flat::from_raw(2.));
+                armor.apply_m1(Flat::from_raw(3.));
+                panic!("interrupted");
+            })
+        }));
+
+        assert!(result.is_err());
+
+        strength.clear_poison();
+        armor.clear_poison();
+
+        assert_eq!((10., 5.), (strength.get(), armor.get()));
+    }
+}